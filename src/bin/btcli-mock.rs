@@ -0,0 +1,78 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! btcli-mock：本地运行的百度翻译模拟服务
+//! 在配置中设置 api_base = "http://127.0.0.1:8787" 即可离线使用 btcli
+
+use btcli_lib::mock::{self, MockOptions};
+use std::env;
+use std::process;
+
+fn show_help() {
+    println!(
+        "btcli-mock - 离线的百度翻译模拟服务\n\n\
+         用法: btcli-mock [选项]\n\n\
+         选项:\n\
+         --listen ADDR       监听地址 (默认: 127.0.0.1:8787)\n\
+         --appid APPID       接受的 appid (默认: mock-appid)\n\
+         --key KEY           用于校验签名的密钥 (默认: mock-key)\n\
+         --error CODE        所有请求都返回该错误码\n\
+         --inject CODE       让接下来的一个请求返回该错误码，可重复指定\n\
+         -h, --help          显示此帮助信息\n"
+    );
+}
+
+fn parse_code(value: Option<&String>) -> usize {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(code) if btcli_lib::fyerrcodes::gen_err_map().contains_key(&code) => code,
+        _ => {
+            eprintln!("错误: 错误码必须是 fyerrcodes 中列出的代码");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut listen = "127.0.0.1:8787".to_string();
+    let mut options = MockOptions::default();
+    let mut inject = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match args[i].as_str() {
+            "-h" | "--help" => {
+                show_help();
+                return;
+            }
+            "--listen" | "--appid" | "--key" if value.is_none() => {
+                eprintln!("错误: {} 需要指定参数", args[i]);
+                process::exit(1);
+            }
+            "--listen" => listen = value.cloned().unwrap_or_default(),
+            "--appid" => options.appid = value.cloned().unwrap_or_default(),
+            "--key" => options.key = value.cloned().unwrap_or_default(),
+            "--error" => options.always_error = Some(parse_code(value)),
+            "--inject" => inject.push(parse_code(value)),
+            other => {
+                eprintln!("错误: 未知参数 {}", other);
+                show_help();
+                process::exit(1);
+            }
+        }
+        i += 2;
+    }
+
+    println!(
+        "btcli-mock 正在监听 http://{} (appid={}, key={})",
+        listen, options.appid, options.key
+    );
+    if let Err(e) = mock::serve(&listen, options, &inject) {
+        eprintln!("启动失败: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 本地翻译缓存
+//! 每条缓存是 .btcli/cache 下的一个 JSON 文件，文件名为 引擎+语言对+原文 的哈希
+
+use crate::conf::CacheConfig;
+use crate::translator::Translation;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// 写入时间（Unix 秒）
+    created: u64,
+    translation: Translation,
+}
+
+/// 缓存统计信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub expired: usize,
+    pub bytes: u64,
+}
+
+pub struct Cache {
+    dir: PathBuf,
+    config: CacheConfig,
+    /// 已知的条目数：首次写入时扫描目录得到，之后随新增条目累加，超过上限时才再次扫描
+    count: AtomicUsize,
+}
+
+/// `count` 尚未扫描目录时的取值
+const UNKNOWN: usize = usize::MAX;
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Cache {
+    /// 打开默认位置（.btcli/cache）的缓存
+    pub fn open(config: &CacheConfig) -> Self {
+        Self::with_dir(crate::conf::config_dir().join("cache"), config)
+    }
+
+    pub fn with_dir(dir: PathBuf, config: &CacheConfig) -> Self {
+        Self {
+            dir,
+            config: config.clone(),
+            count: AtomicUsize::new(UNKNOWN),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 缓存键：引擎、源语言、目标语言和原文共同决定
+    pub fn key(provider: &str, from: &str, to: &str, q: &str) -> String {
+        // 用 \0 分隔，避免 ("a", "bc") 和 ("ab", "c") 拼出相同的字符串
+        let raw = format!("{}\0{}\0{}\0{}", provider, from, to, q);
+        format!("{:x}", md5::compute(raw.as_bytes()))
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn is_expired(&self, created: u64) -> bool {
+        self.config.ttl_secs > 0 && now_secs().saturating_sub(created) > self.config.ttl_secs
+    }
+
+    /// 查询缓存，未命中、已过期或缓存被禁用时返回 None
+    pub fn get(&self, key: &str) -> Option<Translation> {
+        if !self.config.enabled || self.config.refresh {
+            return None;
+        }
+        let raw = fs::read_to_string(self.entry_path(key)).ok()?;
+        let entry: Entry = serde_json::from_str(&raw).ok()?;
+        if self.is_expired(entry.created) {
+            let _ = fs::remove_file(self.entry_path(key));
+            return None;
+        }
+        let mut translation = entry.translation;
+        translation.cached = true;
+        Some(translation)
+    }
+
+    /// 写入缓存，失败时只记录日志，不影响翻译结果
+    pub fn put(&self, key: &str, translation: &Translation) {
+        if !self.config.enabled {
+            return;
+        }
+        let entry = Entry {
+            created: now_secs(),
+            translation: Translation {
+                cached: false,
+                ..translation.clone()
+            },
+        };
+        let path = self.entry_path(key);
+        let is_new = !path.exists();
+        let result = fs::create_dir_all(&self.dir).and_then(|_| {
+            let body = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+            fs::write(&path, body)
+        });
+        match result {
+            Ok(_) if is_new => self.note_insert(),
+            Ok(_) => {}
+            Err(e) => log_to_file!("写入翻译缓存失败: {}", e),
+        }
+    }
+
+    /// 记录新增了一个条目，超过上限时淘汰
+    fn note_insert(&self) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        let count = match self.count.load(Ordering::Relaxed) {
+            UNKNOWN => self.entries().len(),
+            known => known + 1,
+        };
+        let count = if count > self.config.max_entries {
+            self.evict()
+        } else {
+            count
+        };
+        self.count.store(count, Ordering::Relaxed);
+    }
+
+    /// 列出所有缓存文件及其修改时间
+    fn entries(&self) -> Vec<(PathBuf, SystemTime, u64)> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        read_dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                Some((entry.path(), meta.modified().ok()?, meta.len()))
+            })
+            .collect()
+    }
+
+    /// 条目数超过上限时删除最旧的条目，返回剩余的条目数
+    fn evict(&self) -> usize {
+        let mut entries = self.entries();
+        if entries.len() <= self.config.max_entries {
+            return entries.len();
+        }
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        let excess = entries.len() - self.config.max_entries;
+        for (path, _, _) in entries.into_iter().take(excess) {
+            let _ = fs::remove_file(path);
+        }
+        self.config.max_entries
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats::default();
+        for (path, _, len) in self.entries() {
+            stats.entries += 1;
+            stats.bytes += len;
+            let expired = fs::read_to_string(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<Entry>(&raw).ok())
+                .is_none_or(|entry| self.is_expired(entry.created));
+            if expired {
+                stats.expired += 1;
+            }
+        }
+        stats
+    }
+
+    /// 清空缓存，返回删除的条目数
+    pub fn clear(&self) -> std::io::Result<usize> {
+        let mut removed = 0;
+        for (path, _, _) in self.entries() {
+            fs::remove_file(path)?;
+            removed += 1;
+        }
+        self.count.store(0, Ordering::Relaxed);
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translator::TranslationItem;
+
+    fn temp_cache(name: &str, config: CacheConfig) -> Cache {
+        let dir = std::env::temp_dir().join(format!("btcli-cache-test-{}-{}", name, now_secs()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::with_dir(dir, &config)
+    }
+
+    fn sample() -> Translation {
+        Translation {
+            from: "en".to_string(),
+            to: "zh".to_string(),
+            items: vec![TranslationItem::new("Hello", "你好")],
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_put_then_get_marks_cached() {
+        let cache = temp_cache("hit", CacheConfig::default());
+        let key = Cache::key("baidu", "en", "zh", "Hello");
+        cache.put(&key, &sample());
+
+        let hit = cache.get(&key).unwrap();
+        assert!(hit.cached);
+        assert_eq!(hit.text(), "你好");
+        assert_eq!(cache.clear().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_refresh_skips_lookup() {
+        let config = CacheConfig {
+            refresh: true,
+            ..CacheConfig::default()
+        };
+        let cache = temp_cache("refresh", config);
+        let key = Cache::key("baidu", "en", "zh", "Hello");
+        cache.put(&key, &sample());
+
+        assert!(cache.get(&key).is_none());
+        assert_eq!(cache.stats().entries, 1);
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_put_evicts_beyond_max_entries() {
+        let config = CacheConfig {
+            max_entries: 2,
+            ..CacheConfig::default()
+        };
+        let cache = temp_cache("evict", config);
+        for word in ["one", "two", "two", "three"] {
+            cache.put(&Cache::key("baidu", "en", "zh", word), &sample());
+        }
+        assert_eq!(cache.stats().entries, 2);
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_key_depends_on_language_pair() {
+        assert_ne!(
+            Cache::key("baidu", "en", "zh", "Hello"),
+            Cache::key("baidu", "en", "jp", "Hello")
+        );
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 请求录制与回放
+//! 录制模式把每次接口调用的请求和响应追加到磁带文件（JSON Lines），appid、sign、salt 会被抹去；
+//! 回放模式按 引擎及接口变体+源语言+目标语言+原文 查找录制的响应，不访问网络
+
+use crate::conf::NetworkConfig;
+use crate::error::BtcliError;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 不写入磁带的敏感参数
+const REDACTED_PARAMS: [&str; 3] = ["appid", "sign", "salt"];
+const REDACTED: &str = "REDACTED";
+
+/// 回放时用于匹配的请求特征
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RequestKey {
+    /// 引擎及接口变体，领域和术语干预都会改变响应，一并计入：
+    /// baidu、baidu:intervene、baidu:dict、baidu:detect、baidu:field:<领域>
+    pub provider: String,
+    pub from: String,
+    pub to: String,
+    pub q: String,
+}
+
+/// 磁带中的一条记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    #[serde(flatten)]
+    key: RequestKey,
+    url: String,
+    params: Vec<(String, String)>,
+    response: String,
+}
+
+/// 传输模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Live,
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+impl Mode {
+    pub fn from_config(config: &NetworkConfig) -> Self {
+        let path = |p: &Option<String>| p.as_deref().filter(|p| !p.is_empty()).map(PathBuf::from);
+        if let Some(path) = path(&config.replay) {
+            Mode::Replay(path)
+        } else if let Some(path) = path(&config.record) {
+            Mode::Record(path)
+        } else {
+            Mode::Live
+        }
+    }
+}
+
+lazy_static! {
+    /// 已加载的回放磁带，按文件路径缓存
+    static ref TAPES: Mutex<HashMap<PathBuf, HashMap<RequestKey, String>>> =
+        Mutex::new(HashMap::new());
+    /// 录制时串行写入，避免多线程交错
+    static ref RECORD_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// 按传输模式执行一次接口调用：直接发送、发送并录制，或从磁带回放
+pub fn exchange<F>(
+    config: &NetworkConfig,
+    key: RequestKey,
+    url: &str,
+    params: &[(&str, &str)],
+    send: F,
+) -> Result<String, BtcliError>
+where
+    F: FnOnce() -> Result<String, BtcliError>,
+{
+    match Mode::from_config(config) {
+        Mode::Live => send(),
+        Mode::Replay(path) => replay(&path, &key),
+        Mode::Record(path) => {
+            let response = send()?;
+            let interaction = Interaction {
+                key,
+                url: url.to_string(),
+                params: redact(params),
+                response: response.clone(),
+            };
+            if let Err(e) = append(&path, &interaction) {
+                log_to_file!("写入录制文件 {} 失败: {}", path.display(), e);
+            }
+            Ok(response)
+        }
+    }
+}
+
+fn redact(params: &[(&str, &str)]) -> Vec<(String, String)> {
+    params
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_PARAMS.contains(name) {
+                REDACTED
+            } else {
+                value
+            };
+            (name.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+fn append(path: &Path, interaction: &Interaction) -> std::io::Result<()> {
+    let _guard = RECORD_LOCK.lock();
+    let line = serde_json::to_string(interaction).map_err(std::io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+fn replay(path: &Path, key: &RequestKey) -> Result<String, BtcliError> {
+    let mut tapes = match TAPES.lock() {
+        Ok(tapes) => tapes,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if !tapes.contains_key(path) {
+        tapes.insert(path.to_path_buf(), load(path)?);
+    }
+    tapes[path].get(key).cloned().ok_or_else(|| {
+        BtcliError::ReplayMiss(format!(
+            "No recorded response in {} for {} {} -> {}: {}",
+            path.display(),
+            key.provider,
+            key.from,
+            key.to,
+            key.q
+        ))
+    })
+}
+
+/// 读取磁带，同一请求录制了多次时以最后一次为准
+fn load(path: &Path) -> Result<HashMap<RequestKey, String>, BtcliError> {
+    let raw = std::fs::read_to_string(path)?;
+    let mut tape = HashMap::new();
+    for (index, line) in raw.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let interaction: Interaction = serde_json::from_str(line).map_err(|e| {
+            BtcliError::Parse(format!(
+                "Invalid cassette entry at {}:{}: {}",
+                path.display(),
+                index + 1,
+                e
+            ))
+        })?;
+        tape.insert(interaction.key, interaction.response);
+    }
+    Ok(tape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(q: &str) -> RequestKey {
+        RequestKey {
+            provider: "baidu".to_string(),
+            from: "en".to_string(),
+            to: "zh".to_string(),
+            q: q.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_then_replay_redacts_secrets() {
+        let path =
+            std::env::temp_dir().join(format!("btcli-cassette-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let params = [
+            ("appid", "20260101"),
+            ("q", "Hello"),
+            ("sign", "abc"),
+            ("salt", "42"),
+        ];
+
+        let record = NetworkConfig {
+            record: Some(path.display().to_string()),
+            ..NetworkConfig::default()
+        };
+        let response = exchange(&record, key("Hello"), "http://x", &params, || {
+            Ok("{\"trans_result\":[]}".to_string())
+        })
+        .unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("20260101") && !raw.contains("abc"));
+
+        let replay = NetworkConfig {
+            replay: Some(path.display().to_string()),
+            ..NetworkConfig::default()
+        };
+        let replayed = exchange(&replay, key("Hello"), "http://x", &params, || {
+            panic!("replay must not touch the network")
+        })
+        .unwrap();
+        assert_eq!(replayed, response);
+        assert!(matches!(
+            exchange(&replay, key("Bye"), "http://x", &params, || unreachable!()),
+            Err(BtcliError::ReplayMiss(_))
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+}
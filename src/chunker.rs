@@ -0,0 +1,292 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 长文本分块
+//! 按段落、句子切分，保证每块的 UTF-8 字节数不超过接口限制，翻译后按原换行拼回
+
+use crate::error::BtcliError;
+use crate::translator::TranslationItem;
+
+/// 句末标点，中日文与西文都算
+const SENTENCE_ENDS: [char; 10] = ['。', '！', '？', '；', '…', '!', '?', ';', '.', '\u{FF0E}'];
+
+/// 原文中一行（或超长行的一部分）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    /// 所属原文行号
+    pub line: usize,
+    pub text: String,
+    /// 原文中该片段后面是否跟着空白，拼回时用空格连接
+    pub space_after: bool,
+}
+
+/// 一段文本的分块方案
+#[derive(Debug, Clone)]
+pub struct Plan {
+    lines: Vec<String>,
+    chunks: Vec<Vec<Piece>>,
+}
+
+impl Plan {
+    /// 需要发送的块数
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// 第 `index` 块的请求文本，片段之间以换行分隔
+    pub fn chunk_text(&self, index: usize) -> String {
+        self.chunks[index]
+            .iter()
+            .map(|piece| piece.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// 所有块的请求文本
+    pub fn chunk_texts(&self) -> Vec<String> {
+        (0..self.chunks.len()).map(|i| self.chunk_text(i)).collect()
+    }
+
+    /// 第一个包含原文第 `line` 行的块，空行不属于任何块
+    pub fn chunk_of_line(&self, line: usize) -> Option<usize> {
+        self.chunks
+            .iter()
+            .position(|pieces| pieces.iter().any(|piece| piece.line == line))
+    }
+
+    /// 把每块的译文片段拼回原文的行结构，空行原样保留
+    ///
+    /// `translated[i]` 是第 i 块按顺序返回的译文；某块的译文条数与片段数不一致时无法对应回原文行，返回错误
+    pub fn assemble(&self, translated: &[Vec<String>]) -> Result<Vec<TranslationItem>, BtcliError> {
+        let mut dst_lines = vec![String::new(); self.lines.len()];
+        let mut glue = vec![false; self.lines.len()];
+
+        for (index, (pieces, outputs)) in self.chunks.iter().zip(translated).enumerate() {
+            if pieces.len() != outputs.len() {
+                return Err(BtcliError::Parse(format!(
+                    "Chunk {} has {} lines but {} translations were returned",
+                    index + 1,
+                    pieces.len(),
+                    outputs.len()
+                )));
+            }
+            for (piece, output) in pieces.iter().zip(outputs) {
+                let line = &mut dst_lines[piece.line];
+                if glue[piece.line] && !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(output);
+                glue[piece.line] = piece.space_after;
+            }
+        }
+
+        Ok(self
+            .lines
+            .iter()
+            .zip(dst_lines)
+            .map(|(src, dst)| TranslationItem::new(src.clone(), dst))
+            .collect())
+    }
+}
+
+/// 为 `text` 制定分块方案，每块不超过 `max_bytes` 字节
+pub fn plan(text: &str, max_bytes: usize) -> Plan {
+    let max_bytes = max_bytes.max(4);
+    let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+
+    let mut chunks: Vec<Vec<Piece>> = Vec::new();
+    let mut current: Vec<Piece> = Vec::new();
+    let mut current_bytes = 0;
+
+    for (index, line) in lines.iter().enumerate() {
+        // 空行不发送，拼回时原样保留
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        for (text, space_after) in split_line(line, max_bytes) {
+            let needed = if current.is_empty() {
+                text.len()
+            } else {
+                text.len() + 1
+            };
+            if current_bytes + needed > max_bytes && !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+                current_bytes = 0;
+            }
+            current_bytes += if current.is_empty() {
+                text.len()
+            } else {
+                text.len() + 1
+            };
+            current.push(Piece {
+                line: index,
+                text,
+                space_after,
+            });
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    Plan { lines, chunks }
+}
+
+/// 把一行切成不超过 `max_bytes` 的片段：先按句子，再按空白，最后按字符硬切
+fn split_line(line: &str, max_bytes: usize) -> Vec<(String, bool)> {
+    if line.len() <= max_bytes {
+        return vec![(line.to_string(), false)];
+    }
+
+    let mut raw_pieces = Vec::new();
+    for sentence in split_keep(line, is_sentence_end) {
+        if sentence.len() <= max_bytes {
+            raw_pieces.push(sentence);
+            continue;
+        }
+        for word in split_keep(sentence, |c, _| c.is_whitespace()) {
+            if word.len() <= max_bytes {
+                raw_pieces.push(word);
+            } else {
+                raw_pieces.extend(hard_split(word, max_bytes));
+            }
+        }
+    }
+
+    // 贪心合并相邻的小片段
+    let mut merged: Vec<String> = Vec::new();
+    for raw in raw_pieces {
+        match merged.last_mut() {
+            Some(last) if last.len() + raw.len() <= max_bytes => last.push_str(raw),
+            _ => merged.push(raw.to_string()),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|raw| {
+            let space_after = raw.ends_with(char::is_whitespace);
+            (raw.trim_end().to_string(), space_after)
+        })
+        .filter(|(text, _)| !text.is_empty())
+        .collect()
+}
+
+/// 句末判断：中日文标点直接断开，西文句点需后跟空白
+fn is_sentence_end(c: char, next: Option<char>) -> bool {
+    if !SENTENCE_ENDS.contains(&c) {
+        return false;
+    }
+    if c.is_ascii() {
+        next.is_none_or(char::is_whitespace)
+    } else {
+        true
+    }
+}
+
+/// 在满足 `is_end` 的字符之后切开，并把紧随其后的空白留在前一段
+fn split_keep<F>(s: &str, is_end: F) -> Vec<&str>
+where
+    F: Fn(char, Option<char>) -> bool,
+{
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        if is_end(c, next) {
+            // 吞掉后续空白
+            while let Some(&(_, n)) = chars.peek() {
+                if n.is_whitespace() && !is_end(n, None) {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let end = chars.peek().map_or(s.len(), |&(i, _)| i);
+            parts.push(&s[start..end]);
+            start = end;
+        }
+    }
+    if start < s.len() {
+        parts.push(&s[start..]);
+    }
+    parts
+}
+
+/// 按字符边界硬切，不会截断多字节字符
+fn hard_split(s: &str, max_bytes: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < s.len() {
+        let mut end = (start + max_bytes).min(s.len());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        parts.push(&s[start..end]);
+        start = end;
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_single_chunk() {
+        let plan = plan("Hello\n\nWorld", 6000);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan.chunk_text(0), "Hello\nWorld");
+    }
+
+    #[test]
+    fn test_chunks_respect_byte_limit() {
+        let text = "这是第一句。这是第二句！这是第三句？".repeat(20);
+        let plan = plan(&text, 100);
+        assert!(plan.len() > 1);
+        for chunk in plan.chunk_texts() {
+            assert!(chunk.len() <= 100);
+        }
+    }
+
+    #[test]
+    fn test_assemble_preserves_line_breaks() {
+        let plan = plan("One. Two.\n\nThree", 6);
+        let translated: Vec<Vec<String>> = plan
+            .chunk_texts()
+            .iter()
+            .map(|chunk| chunk.lines().map(|l| l.to_uppercase()).collect())
+            .collect();
+        let items = plan.assemble(&translated).unwrap();
+        let text: Vec<&str> = items.iter().map(|item| item.dst.as_str()).collect();
+        assert_eq!(text, vec!["ONE. TWO.", "", "THREE"]);
+    }
+
+    #[test]
+    fn test_assemble_rejects_mismatched_chunk() {
+        let plan = plan("One\nTwo\nThree", 6000);
+        let translated = vec![vec!["一 二 三".to_string()]];
+        assert!(matches!(
+            plan.assemble(&translated),
+            Err(BtcliError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_chunk_of_line() {
+        let plan = plan("One. Two.\n\nThree", 6);
+        assert_eq!(plan.chunk_of_line(0), Some(0));
+        assert_eq!(plan.chunk_of_line(1), None);
+        assert_eq!(plan.chunk_of_line(2), Some(plan.len() - 1));
+    }
+}
@@ -5,7 +5,261 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::completions::Shell;
+use crate::encoding::TextEncoding;
+use crate::error::{BtcliError, exit};
+use crate::output::OutputFormat;
+use crate::pool::Progress;
+use crate::translator::Translation;
 use std::env;
+use std::fmt;
+use std::io::{IsTerminal, Write};
+
+/// 选项的取值方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// 开关，不带值
+    Flag,
+    /// 必须带值，括号内为帮助中显示的占位符
+    Required(&'static str),
+    /// 值可省略
+    Optional(&'static str),
+}
+
+/// 命令行选项定义，解析、帮助信息和补全脚本共用
+#[derive(Debug, Clone, Copy)]
+pub struct OptSpec {
+    pub short: Option<char>,
+    pub long: &'static str,
+    pub value: ValueKind,
+    pub help: &'static str,
+}
+
+/// 子命令定义
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// 参数用法，例如 `stats|clear`
+    pub args: &'static str,
+    /// 第一个参数可选的操作，用于拼写提示和补全
+    pub actions: &'static [&'static str],
+    pub help: &'static str,
+}
+
+const fn opt(
+    short: Option<char>,
+    long: &'static str,
+    value: ValueKind,
+    help: &'static str,
+) -> OptSpec {
+    OptSpec {
+        short,
+        long,
+        value,
+        help,
+    }
+}
+
+/// 全部选项，顺序即帮助信息中的顺序
+pub const OPTIONS: &[OptSpec] = &[
+    opt(Some('s'), "source", ValueKind::Required("LANG"), "指定源语言 (例如: en, zh, ja, zh-TW)"),
+    opt(Some('t'), "target", ValueKind::Required("LANG"), "指定目标语言 (例如: en, zh, ja, zh-TW)"),
+    opt(Some('d'), "dict", ValueKind::Flag, "查词模式，显示音标、词性释义和发音链接"),
+    opt(Some('f'), "file", ValueKind::Required("FILE"), "翻译文本文件，自动识别 UTF-8、UTF-8 BOM、GBK/GB18030、UTF-16 编码"),
+    opt(Some('o'), "output", ValueKind::Required("FILE"), "把译文写入文件（默认输出到标准输出）"),
+    opt(None, "format", ValueKind::Required("FMT"), "输出格式：text（默认）、json、jsonl、tsv；json/jsonl 下错误也输出为 JSON"),
+    opt(None, "encoding", ValueKind::Required("ENC"), "译文编码：utf-8（默认）、utf-8-bom、gbk、gb18030、utf-16le、utf-16be"),
+    opt(None, "domain", ValueKind::Required("DOMAIN"), "使用垂直领域翻译 (例如: it, finance, senimed)"),
+    opt(None, "no-cache", ValueKind::Flag, "不使用本地翻译缓存"),
+    opt(None, "refresh", ValueKind::Flag, "忽略已有缓存，重新翻译并更新缓存"),
+    opt(None, "batch", ValueKind::Optional("FILE"), "批量翻译文件（省略或 - 时读标准输入）中的每一行，按原顺序输出"),
+    opt(None, "stream", ValueKind::Flag, "边读标准输入边逐行翻译（保留颜色，跳过已是目标语言的行）"),
+    opt(None, "record", ValueKind::Required("FILE"), "把请求与响应录制到文件（抹去 appid、sign、salt）"),
+    opt(None, "replay", ValueKind::Required("FILE"), "从录制文件回放，不访问网络"),
+    opt(None, "listen", ValueKind::Required("ADDR"), "serve 的监听地址（默认 127.0.0.1:8788）"),
+    opt(Some('h'), "help", ValueKind::Flag, "显示此帮助信息"),
+    opt(Some('v'), "version", ValueKind::Flag, "显示版本信息"),
+];
+
+/// 全部子命令，顺序即帮助信息中的顺序
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "translate", args: "[文本]", actions: &[], help: "翻译文本（默认命令，可省略）" },
+    CommandSpec { name: "detect", args: "[文本]", actions: &[], help: "识别文本语种（省略文本时读取标准输入）" },
+    CommandSpec { name: "langs", args: "", actions: &[], help: "列出支持的语种及别名" },
+    CommandSpec { name: "config", args: "[show|path]", actions: &["show", "path"], help: "显示当前配置（key 已隐藏）或配置文件路径" },
+    CommandSpec { name: "history", args: "[N|clear]", actions: &["clear"], help: "显示最近 N 条（默认 20）翻译历史，或清空历史" },
+    CommandSpec { name: "cache", args: "stats|clear", actions: &["stats", "clear"], help: "查看缓存统计或清空翻译缓存" },
+    CommandSpec { name: "glossary", args: "check <原文文件> <译文文件>", actions: &["check"], help: "检查译文是否符合术语表" },
+    CommandSpec { name: "repl", args: "", actions: &[], help: "行模式交互翻译，支持 :to、:from、:swap、:copy、:dict" },
+    CommandSpec { name: "exec", args: "[--] <命令> [参数...]", actions: &[], help: "运行命令并逐行翻译它的输出，退出码与命令一致" },
+    CommandSpec { name: "serve", args: "[--listen ADDR]", actions: &[], help: "在本机提供 JSON 翻译接口" },
+    CommandSpec { name: "doctor", args: "", actions: &[], help: "检查配置、凭据、网络和接口连通性" },
+    CommandSpec { name: "completions", args: "bash|zsh|fish", actions: &["bash", "zsh", "fish"], help: "生成 shell 补全脚本" },
+    CommandSpec { name: "manpage", args: "", actions: &[], help: "生成 man 手册页（roff 格式）" },
+];
+
+/// 缓存管理操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    Stats,
+    Clear,
+}
+
+/// 翻译历史操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    /// 显示最近的若干条
+    List(usize),
+    Clear,
+}
+
+/// 不读取配置文件的子命令，在加载配置之前执行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandaloneCommand {
+    /// btcli completions bash|zsh|fish
+    Completions(Shell),
+    /// btcli config path
+    ConfigPath,
+    /// btcli doctor，自行检查配置文件
+    Doctor,
+    /// btcli langs，列出支持的语种及别名
+    Langs,
+    /// btcli manpage
+    Manpage,
+}
+
+/// 翻译以外、需要配置文件的子命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// btcli cache stats|clear
+    Cache(CacheAction),
+    /// btcli config [show]
+    Config,
+    /// btcli detect <文本>，文本放在 `CliArgs.text`
+    Detect,
+    /// btcli glossary check <原文文件> <译文文件>
+    GlossaryCheck { source: String, translated: String },
+    /// btcli history [N|clear]
+    History(HistoryAction),
+    /// btcli exec -- <命令> [参数...]，运行命令并逐行翻译其输出
+    Exec(Vec<String>),
+    /// btcli repl
+    Repl,
+    /// btcli serve [--listen ADDR]
+    Serve,
+}
+
+/// 命令行解析错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// 未知选项，附带拼写最接近的选项
+    UnknownOption {
+        option: String,
+        suggestion: Option<String>,
+    },
+    /// 选项缺少值
+    MissingValue(String),
+    /// 选项的值不合法
+    InvalidValue { option: String, message: String },
+    /// 子命令缺少参数
+    MissingArgument { command: &'static str },
+    /// 子命令不支持的操作，附带拼写最接近的操作
+    UnknownAction {
+        command: &'static str,
+        action: String,
+        suggestion: Option<&'static str>,
+    },
+    /// 子命令不接受的多余参数
+    UnexpectedArgument {
+        command: &'static str,
+        argument: String,
+    },
+    /// 不能同时使用的两个选项
+    Conflict(&'static str, &'static str),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        exit::USAGE
+    }
+}
+
+fn command_usage(name: &str) -> String {
+    let args = COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .map_or("", |spec| spec.args);
+    format!("btcli {} {}", name, args).trim_end().to_string()
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownOption { option, suggestion } => {
+                write!(f, "未知选项 {}", option)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, "，是否想使用 {}？", suggestion)?;
+                }
+                Ok(())
+            }
+            CliError::MissingValue(option) => write!(f, "{} 需要指定参数值", option),
+            CliError::InvalidValue { option, message } => {
+                write!(f, "{} 的参数无效: {}", option, message)
+            }
+            CliError::MissingArgument { command } => {
+                write!(f, "{} 缺少参数，用法: {}", command, command_usage(command))
+            }
+            CliError::UnknownAction {
+                command,
+                action,
+                suggestion,
+            } => {
+                write!(f, "{} 不支持 {}", command, action)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "，是否想使用 {}？", suggestion),
+                    None => write!(f, "，用法: {}", command_usage(command)),
+                }
+            }
+            CliError::UnexpectedArgument { command, argument } => {
+                write!(f, "{} 不接受参数 {}，用法: {}", command, argument, command_usage(command))
+            }
+            CliError::Conflict(a, b) => write!(f, "{} 与 {} 不能同时使用", a, b),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// 编辑距离，用于“是否想使用”的提示
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// 在候选中找拼写最接近的一个，差异太大时不给提示
+pub(crate) fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (input.chars().count() / 3).clamp(1, 3);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
 
 /// 命令行参数结构
 #[derive(Debug, Clone)]
@@ -15,6 +269,37 @@ pub struct CliArgs {
     pub target_lang: Option<String>,
     pub help: bool,
     pub version: bool,
+    /// 不读也不写缓存
+    pub no_cache: bool,
+    /// 忽略已有缓存，重新请求并更新缓存
+    pub refresh: bool,
+    /// 垂直领域翻译
+    pub domain: Option<String>,
+    /// 查词模式，显示词典释义和发音
+    pub dict: bool,
+    /// 把请求与响应录制到文件
+    pub record: Option<String>,
+    /// 从录制文件回放，不访问网络
+    pub replay: Option<String>,
+    /// 批量模式的输入文件，每行一条，`-` 表示标准输入
+    pub batch: Option<String>,
+    /// 从标准输入读取要翻译的文本（参数 `-`）
+    pub stdin: bool,
+    /// 把标准输入当作行流，边读边翻译
+    pub stream: bool,
+    /// 要翻译的文本文件，编码自动识别
+    pub file: Option<String>,
+    /// 译文输出文件，未指定时输出到标准输出
+    pub output: Option<String>,
+    /// 译文的输出编码，默认 UTF-8
+    pub encoding: Option<TextEncoding>,
+    /// 输出格式
+    pub format: OutputFormat,
+    /// serve 的监听地址
+    pub listen: Option<String>,
+    pub command: Option<Command>,
+    /// 不需要配置文件的子命令，与 `command` 至多设置一个
+    pub standalone: Option<StandaloneCommand>,
 }
 
 impl CliArgs {
@@ -25,76 +310,493 @@ impl CliArgs {
             target_lang: None,
             help: false,
             version: false,
+            no_cache: false,
+            refresh: false,
+            domain: None,
+            dict: false,
+            record: None,
+            replay: None,
+            batch: None,
+            stdin: false,
+            stream: false,
+            file: None,
+            output: None,
+            encoding: None,
+            format: OutputFormat::Text,
+            listen: None,
+            command: None,
+            standalone: None,
+        }
+    }
+
+    /// 处理一个选项，`value` 为 `--name=value` 中的值或下一个参数
+    fn apply(&mut self, spec: &OptSpec, option: &str, value: Option<String>) -> Result<(), CliError> {
+        let invalid = |e: BtcliError| CliError::InvalidValue {
+            option: option.to_string(),
+            message: e.to_string().trim_start_matches(":( ").to_string(),
+        };
+        let value = || value.clone().ok_or_else(|| CliError::MissingValue(option.to_string()));
+        match spec.long {
+            "source" => {
+                let code = crate::langs::normalize_source(&value()?).map_err(invalid)?;
+                self.source_lang = Some(code);
+            }
+            "target" => {
+                let code = crate::langs::normalize_target(&value()?).map_err(invalid)?;
+                self.target_lang = Some(code);
+            }
+            "dict" => self.dict = true,
+            "file" => self.file = Some(value()?),
+            "output" => self.output = Some(value()?),
+            "format" => self.format = OutputFormat::parse(&value()?).map_err(invalid)?,
+            "encoding" => self.encoding = Some(TextEncoding::parse(&value()?).map_err(invalid)?),
+            "domain" => {
+                let domain = value()?;
+                if !crate::fycore::is_valid_domain(&domain) {
+                    return Err(CliError::InvalidValue {
+                        option: option.to_string(),
+                        message: format!("不支持的领域 {}", domain),
+                    });
+                }
+                self.domain = Some(domain);
+            }
+            "no-cache" => self.no_cache = true,
+            "refresh" => self.refresh = true,
+            // 文件参数可省略，省略时读取标准输入
+            "batch" => self.batch = Some(value().unwrap_or_else(|_| "-".to_string())),
+            "stream" => self.stream = true,
+            "record" => self.record = Some(value()?),
+            "replay" => self.replay = Some(value()?),
+            "listen" => self.listen = Some(value()?),
+            "help" => self.help = true,
+            "version" => self.version = true,
+            _ => unreachable!("option {} has no handler", spec.long),
+        }
+        Ok(())
+    }
+}
+
+/// 按 `-x` / `--name` 查找选项定义
+fn find_option(name: &str) -> Option<&'static OptSpec> {
+    match name.strip_prefix("--") {
+        Some(long) => OPTIONS.iter().find(|spec| spec.long == long),
+        None => {
+            let mut chars = name.strip_prefix('-')?.chars();
+            let (Some(short), None) = (chars.next(), chars.next()) else {
+                return None;
+            };
+            OPTIONS.iter().find(|spec| spec.short == Some(short))
+        }
+    }
+}
+
+/// 是否为选项：以 - 开头，但不是单独的 -，也不是负数
+fn is_option(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg.parse::<f64>().is_err()
+}
+
+/// 解析出的子命令
+enum Parsed {
+    Translate,
+    Command(Command),
+    Standalone(StandaloneCommand),
+}
+
+/// 把位置参数组装为子命令
+fn build_command(name: &'static str, mut args: Vec<String>) -> Result<Parsed, CliError> {
+    let unexpected = |argument: &String| CliError::UnexpectedArgument {
+        command: name,
+        argument: argument.clone(),
+    };
+    let actions = COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .map_or(&[][..], |spec| spec.actions);
+    let unknown_action = |action: &str| CliError::UnknownAction {
+        command: name,
+        action: action.to_string(),
+        suggestion: suggest(action, actions.iter().copied()),
+    };
+    let command = match name {
+        "translate" => Parsed::Translate,
+        "detect" => Parsed::Command(Command::Detect),
+        "langs" | "serve" | "doctor" | "manpage" | "repl" => {
+            if let Some(extra) = args.first() {
+                return Err(unexpected(extra));
+            }
+            match name {
+                "langs" => Parsed::Standalone(StandaloneCommand::Langs),
+                "serve" => Parsed::Command(Command::Serve),
+                "manpage" => Parsed::Standalone(StandaloneCommand::Manpage),
+                "repl" => Parsed::Command(Command::Repl),
+                _ => Parsed::Standalone(StandaloneCommand::Doctor),
+            }
+        }
+        "config" => {
+            let parsed = match args.first().map(String::as_str) {
+                None | Some("show") => Parsed::Command(Command::Config),
+                Some("path") => Parsed::Standalone(StandaloneCommand::ConfigPath),
+                Some(other) => return Err(unknown_action(other)),
+            };
+            if let Some(extra) = args.get(1) {
+                return Err(unexpected(extra));
+            }
+            parsed
+        }
+        "history" => {
+            let action = match args.first().map(String::as_str) {
+                None => HistoryAction::List(20),
+                Some("clear") => HistoryAction::Clear,
+                Some(other) => match other.parse() {
+                    Ok(count) => HistoryAction::List(count),
+                    Err(_) => return Err(unknown_action(other)),
+                },
+            };
+            if let Some(extra) = args.get(1) {
+                return Err(unexpected(extra));
+            }
+            Parsed::Command(Command::History(action))
+        }
+        "cache" => {
+            let action = match args.first().map(String::as_str) {
+                None => return Err(CliError::MissingArgument { command: name }),
+                Some("stats") => CacheAction::Stats,
+                Some("clear") => CacheAction::Clear,
+                Some(other) => return Err(unknown_action(other)),
+            };
+            if let Some(extra) = args.get(1) {
+                return Err(unexpected(extra));
+            }
+            Parsed::Command(Command::Cache(action))
+        }
+        "completions" => {
+            let shell = match args.first().map(String::as_str) {
+                None => return Err(CliError::MissingArgument { command: name }),
+                Some(other) => Shell::parse(other).ok_or_else(|| unknown_action(other))?,
+            };
+            if let Some(extra) = args.get(1) {
+                return Err(unexpected(extra));
+            }
+            Parsed::Standalone(StandaloneCommand::Completions(shell))
+        }
+        "glossary" => match args.first().map(String::as_str) {
+            None => return Err(CliError::MissingArgument { command: name }),
+            Some("check") if args.len() < 3 => {
+                return Err(CliError::MissingArgument { command: name });
+            }
+            Some("check") => {
+                if let Some(extra) = args.get(3) {
+                    return Err(unexpected(extra));
+                }
+                let translated = args.remove(2);
+                let source = args.remove(1);
+                Parsed::Command(Command::GlossaryCheck { source, translated })
+            }
+            Some(other) => return Err(unknown_action(other)),
+        },
+        "exec" => {
+            if args.is_empty() {
+                return Err(CliError::MissingArgument { command: name });
+            }
+            Parsed::Command(Command::Exec(args))
+        }
+        _ => unreachable!("command {} has no handler", name),
+    };
+    Ok(command)
+}
+
+impl CliArgs {
+    fn set_command(&mut self, parsed: Parsed) {
+        match parsed {
+            Parsed::Translate => {}
+            Parsed::Command(command) => self.command = Some(command),
+            Parsed::Standalone(command) => self.standalone = Some(command),
         }
     }
 }
 
 /// 解析命令行参数
-pub fn parse_args(args: &[String]) -> CliArgs {
+///
+/// 第一个位置参数若是子命令名则作为子命令，否则按 translate 处理；`--` 之后的参数都作为位置参数，
+/// exec 的第一个位置参数起全部属于要运行的命令
+pub fn parse_args(args: &[String]) -> Result<CliArgs, CliError> {
     let mut cli_args = CliArgs::new();
+    let mut command: Option<&'static str> = None;
+    let mut positionals: Vec<String> = Vec::new();
     let mut i = 0;
-    
+
     while i < args.len() {
         let arg = &args[i];
-        
-        match arg.as_str() {
-            "-h" | "--help" => {
-                cli_args.help = true;
-                i += 1;
-            }
-            "-v" | "--version" => {
-                cli_args.version = true;
-                i += 1;
-            }
-            "-s" | "--source" => {
-                if i + 1 < args.len() {
-                    cli_args.source_lang = Some(args[i + 1].clone());
-                    i += 2;
-                } else {
-                    eprintln!("错误: --source 需要指定源语言参数");
-                    std::process::exit(1);
+        if arg == "--" {
+            positionals.extend_from_slice(&args[i + 1..]);
+            break;
+        }
+        if command == Some("exec") && !is_option(arg) && arg != "-" {
+            positionals.extend_from_slice(&args[i..]);
+            break;
+        }
+        if arg == "-" {
+            cli_args.stdin = true;
+            i += 1;
+            continue;
+        }
+        if !is_option(arg) {
+            match COMMANDS.iter().find(|spec| spec.name == arg) {
+                Some(spec) if command.is_none() && positionals.is_empty() => {
+                    command = Some(spec.name)
                 }
+                _ => positionals.push(arg.clone()),
             }
-            "-t" | "--target" => {
-                if i + 1 < args.len() {
-                    cli_args.target_lang = Some(args[i + 1].clone());
-                    i += 2;
-                } else {
-                    eprintln!("错误: --target 需要指定目标语言参数");
-                    std::process::exit(1);
-                }
+            i += 1;
+            continue;
+        }
+
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let Some(spec) = find_option(name) else {
+            let suggestion = name.strip_prefix("--").and_then(|long| {
+                suggest(long, OPTIONS.iter().map(|spec| spec.long))
+                    .map(|long| format!("--{}", long))
+            });
+            return Err(CliError::UnknownOption {
+                option: name.to_string(),
+                suggestion,
+            });
+        };
+        i += 1;
+        let value = match spec.value {
+            ValueKind::Flag if inline.is_some() => {
+                return Err(CliError::InvalidValue {
+                    option: name.to_string(),
+                    message: "该选项不接受参数值".to_string(),
+                });
             }
-            _ => {
-                // 如果不是选项，则认为是翻译文本的一部分
-                if cli_args.text.is_empty() {
-                    cli_args.text = arg.clone();
-                } else {
-                    cli_args.text.push(' ');
-                    cli_args.text.push_str(arg);
-                }
+            ValueKind::Flag => None,
+            _ if inline.is_some() => inline,
+            ValueKind::Required(_) => {
+                let value = args.get(i).cloned();
                 i += 1;
+                value
             }
+            ValueKind::Optional(_) => match args.get(i) {
+                Some(next) if next == "-" || !next.starts_with('-') => {
+                    i += 1;
+                    Some(next.clone())
+                }
+                _ => None,
+            },
+        };
+        cli_args.apply(spec, name, value)?;
+    }
+
+    if cli_args.record.is_some() && cli_args.replay.is_some() {
+        return Err(CliError::Conflict("--record", "--replay"));
+    }
+    if cli_args.listen.is_some() && command != Some("serve") {
+        return Err(CliError::InvalidValue {
+            option: "--listen".to_string(),
+            message: "只能与 serve 子命令一起使用".to_string(),
+        });
+    }
+
+    match command {
+        None | Some("translate") | Some("detect") => {
+            cli_args.set_command(build_command(command.unwrap_or("translate"), Vec::new())?);
+            cli_args.text = positionals.join(" ");
+        }
+        Some(name) => cli_args.set_command(build_command(name, positionals)?),
+    }
+    Ok(cli_args)
+}
+
+/// 把查词结果渲染为词典卡片：译文、音标释义和发音链接
+pub fn format_dict_card(result: &Translation) -> String {
+    let mut card = String::new();
+    for item in &result.items {
+        card.push_str(&format!("{} -> {}\n", item.src, item.dst));
+        if let Some(dict) = &item.dict {
+            card.push_str(&dict.to_string());
+        }
+        if let Some(url) = &item.src_tts {
+            card.push_str(&format!("  原文发音: {}\n", url));
+        }
+        if let Some(url) = &item.dst_tts {
+            card.push_str(&format!("  译文发音: {}\n", url));
+        }
+    }
+    card
+}
+
+/// 是否应从标准输入读取文本：显式给出 `-`，或没有提供文本且标准输入不是终端（管道、重定向）
+pub fn wants_stdin(cli_args: &CliArgs) -> bool {
+    let needs_text = cli_args.standalone.is_none()
+        && matches!(cli_args.command, None | Some(Command::Detect))
+        && !cli_args.stream;
+    cli_args.stdin
+        || (needs_text
+            && cli_args.text.is_empty()
+            && cli_args.batch.is_none()
+            && cli_args.file.is_none()
+            && !std::io::stdin().is_terminal())
+}
+
+/// 读取标准输入的全部内容，去掉末尾的换行
+pub fn read_stdin() -> std::io::Result<String> {
+    let mut text = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+    let trimmed = text.trim_end_matches(['\r', '\n']).len();
+    text.truncate(trimmed);
+    Ok(text)
+}
+
+/// 把译文按指定编码写到 `-o` 指定的文件，未指定时写到标准输出
+pub fn write_output(text: &str, cli_args: &CliArgs) -> std::io::Result<()> {
+    let bytes = cli_args.encoding.unwrap_or(TextEncoding::Utf8).encode(text);
+    match &cli_args.output {
+        Some(path) => std::fs::write(path, bytes),
+        None => {
+            let mut stdout = std::io::stdout();
+            stdout.write_all(&bytes)?;
+            stdout.flush()
+        }
+    }
+}
+
+/// 输出错误：json/jsonl 格式下向标准输出打印带错误码的 JSON 对象，否则在标准错误打印说明和建议
+///
+/// 返回该错误对应的进程退出码
+pub fn report_error(context: &str, error: &BtcliError, format: OutputFormat) -> i32 {
+    if format.is_json() {
+        println!("{}", crate::output::error_json(error));
+    } else {
+        eprintln!("{}: {}", context, error);
+        if let Some(hint) = error.hint() {
+            eprintln!("提示: {}", hint);
+        }
+    }
+    error.exit_code()
+}
+
+/// 在标准错误上绘制进度条，只有一个请求或标准错误不是终端时不显示
+pub fn render_progress(progress: Progress) {
+    let mut stderr = std::io::stderr();
+    if progress.total <= 1 || !stderr.is_terminal() {
+        return;
+    }
+    const WIDTH: usize = 30;
+    let filled = WIDTH * progress.done / progress.total;
+    let _ = write!(
+        stderr,
+        "\r翻译进度 [{}{}] {}/{}",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        progress.done,
+        progress.total
+    );
+    // 完成后清除进度条，不与译文混在一起
+    if progress.done == progress.total {
+        let _ = write!(stderr, "\r\x1b[K");
+    }
+    let _ = stderr.flush();
+}
+
+/// 用法行：(命令行, 说明)
+pub const USAGE: &[(&str, &str)] = &[
+    ("btcli [选项] [translate] <文本>", "翻译文本"),
+    ("btcli [选项] -", "从标准输入读取文本"),
+    ("btcli [选项] -f FILE [-o FILE]", "翻译文本文件"),
+    ("... | btcli [选项]", "管道输入"),
+    ("... | btcli --stream [选项]", "逐行流式翻译"),
+    ("btcli <命令> [参数] [选项]", "运行子命令"),
+];
+
+/// 退出码及含义
+pub const EXIT_CODES: &[(i32, &str)] = &[
+    (exit::OK, "成功"),
+    (exit::FAILURE, "其他错误"),
+    (exit::USAGE, "用法错误"),
+    (exit::CONFIG, "配置错误"),
+    (exit::NETWORK, "网络错误"),
+    (exit::AUTH, "认证失败(52003/54001)"),
+    (exit::QUOTA, "余额不足(54004)"),
+    (exit::RATE_LIMIT, "频率受限(54003/54005)"),
+    (exit::CONTENT, "内容被拒绝(20003)"),
+    (exit::UNSUPPORTED_LANG, "不支持的语种(58001)"),
+    (exit::REPLAY_MISS, "回放时没有匹配的录制"),
+    (exit::CANCELLED, "已取消"),
+];
+
+/// 示例：(命令行, 说明)
+pub const EXAMPLES: &[(&str, &str)] = &[
+    ("btcli \"Hello world\"", "翻译文本"),
+    ("btcli -t zh \"Hello world\"", "翻译为中文"),
+    ("btcli -s en -t zh \"Hello world\"", "指定源语言和目标语言"),
+    ("btcli -d hello", "查词"),
+    ("btcli --batch words.txt -t zh", "逐行批量翻译"),
+    ("btcli --format json -t zh hello", "输出含语种、片段、缓存命中和耗时的 JSON"),
+    ("btcli -f old.txt -o new.txt -t en", "翻译文件（如 GBK 编码）并以 UTF-8 写出"),
+    ("cat README | btcli -t zh", "作为管道过滤器使用"),
+    ("make 2>&1 | btcli --stream -t zh", "实时翻译构建输出"),
+    ("btcli exec -t zh -- cargo build", "运行命令并翻译输出"),
+    ("btcli repl -t ja", "交互翻译为日语"),
+    ("btcli translate -- --help", "翻译以 - 开头的文本"),
+    ("btcli completions bash > /etc/bash_completion.d/btcli", "安装 bash 补全"),
+];
+
+/// 选项在帮助中的写法，例如 `-s, --source LANG`
+pub fn option_synopsis(spec: &OptSpec) -> String {
+    let mut synopsis = match spec.short {
+        Some(short) => format!("-{}, --{}", short, spec.long),
+        None => format!("--{}", spec.long),
+    };
+    match spec.value {
+        ValueKind::Flag => {}
+        ValueKind::Required(value) => synopsis.push_str(&format!(" {}", value)),
+        ValueKind::Optional(value) => synopsis.push_str(&format!(" [{}]", value)),
+    }
+    synopsis
+}
+
+/// 两列对齐的列表，左列过长时说明另起一行
+fn two_columns(rows: &[(String, &str)]) -> String {
+    const WIDTH: usize = 34;
+    let mut out = String::new();
+    for (left, right) in rows {
+        let left = format!("  {}", left);
+        if crate::langs::display_width(&left) >= WIDTH - 1 {
+            out.push_str(&format!("{}\n{}{}\n", left, " ".repeat(WIDTH), right));
+        } else {
+            out.push_str(&format!("{}{}\n", crate::langs::pad(&left, WIDTH), right));
         }
     }
-    
-    cli_args
+    out
+}
+
+/// 帮助信息，由 [`OPTIONS`]、[`COMMANDS`] 等定义生成
+pub fn help_text() -> String {
+    let usage: Vec<(String, &str)> = USAGE.iter().map(|(line, help)| (line.to_string(), *help)).collect();
+    let options: Vec<(String, &str)> = OPTIONS.iter().map(|spec| (option_synopsis(spec), spec.help)).collect();
+    let commands: Vec<(String, &str)> = COMMANDS
+        .iter()
+        .map(|spec| (format!("{} {}", spec.name, spec.args).trim_end().to_string(), spec.help))
+        .collect();
+    let exit_codes: Vec<(String, &str)> = EXIT_CODES.iter().map(|(code, help)| (code.to_string(), *help)).collect();
+    let examples: Vec<(String, &str)> = EXAMPLES.iter().map(|(line, help)| (line.to_string(), *help)).collect();
+    format!(
+        "btcli - 命令行翻译工具\n\n用法:\n{}\n选项:\n{}\n命令:\n{}\n退出码:\n{}\n示例:\n{}",
+        two_columns(&usage),
+        two_columns(&options),
+        two_columns(&commands),
+        two_columns(&exit_codes),
+        two_columns(&examples)
+    )
 }
 
 /// 显示帮助信息
 pub fn show_help() {
-    println!(
-        "btcli - 命令行翻译工具\n\n\
-         用法: btcli [选项] <文本>\n\n\
-         选项:\n\
-         -s, --source LANG    指定源语言 (例如: en, zh)\n\
-         -t, --target LANG    指定目标语言 (例如: en, zh)\n\
-         -h, --help          显示此帮助信息\n\
-         -v, --version       显示版本信息\n\n\
-         示例:\n\
-         btcli \"Hello world\"                 # 翻译文本\n\
-         btcli -t zh \"Hello world\"          # 翻译为中文\n\
-         btcli -s en -t zh \"Hello world\"   # 指定源语言和目标语言\n"
-    );
+    print!("{}", help_text());
 }
 
 /// 显示版本信息
@@ -0,0 +1,381 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! shell 补全脚本生成（`btcli completions bash|zsh|fish`）
+//! 选项和子命令取自 [`crate::cli`] 中与帮助信息相同的定义，`-s/-t` 的取值取自语种表
+
+use crate::cli::{COMMANDS, OPTIONS, OptSpec, ValueKind};
+use crate::encoding::TextEncoding;
+use crate::langs::LANGS;
+use crate::output::OutputFormat;
+
+/// 支持生成补全脚本的 shell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+}
+
+/// 选项值的补全方式
+enum Values {
+    /// 任意文本，不补全
+    Any,
+    Files,
+    /// 固定的取值及说明，说明可以为空
+    Words(Vec<(&'static str, &'static str)>),
+}
+
+fn option_values(spec: &OptSpec) -> Values {
+    let plain = |names: &[&'static str]| Values::Words(names.iter().map(|n| (*n, "")).collect());
+    match spec.long {
+        "source" => Values::Words(LANGS.iter().map(|l| (l.code, l.name_zh)).collect()),
+        "target" => Values::Words(
+            LANGS
+                .iter()
+                .filter(|l| l.is_target())
+                .map(|l| (l.code, l.name_zh))
+                .collect(),
+        ),
+        "domain" => Values::Words(crate::fycore::BAIDU_DOMAINS.to_vec()),
+        "format" => plain(OutputFormat::NAMES),
+        "encoding" => plain(TextEncoding::NAMES),
+        _ => match spec.value {
+            ValueKind::Required("FILE") | ValueKind::Optional("FILE") => Values::Files,
+            _ => Values::Any,
+        },
+    }
+}
+
+/// 选项的全部写法，例如 `-s`、`--source`
+fn option_names(spec: &OptSpec) -> Vec<String> {
+    let mut names: Vec<String> = spec.short.map(|c| format!("-{}", c)).into_iter().collect();
+    names.push(format!("--{}", spec.long));
+    names
+}
+
+fn takes_value(spec: &&OptSpec) -> bool {
+    spec.value != ValueKind::Flag
+}
+
+/// 生成指定 shell 的补全脚本
+pub fn generate(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+const BASH_TEMPLATE: &str = r#"# btcli 的 bash 补全脚本，由 `btcli completions bash` 生成
+
+_btcli() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    COMPREPLY=()
+
+    case "$prev" in
+@VALUES@    esac
+
+    # 找出子命令及其位置，跳过选项和选项的值
+    local cmd="" cmd_index=0 i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${COMP_WORDS[i]}" in
+            @TAKES_VALUE@) ((i++)) ;;
+            --) break ;;
+            -*) ;;
+            *) cmd="${COMP_WORDS[i]}"; cmd_index=$i; break ;;
+        esac
+    done
+
+    # exec 的命令之后的参数属于被运行的命令
+    if [[ "$cmd" == exec ]] && ((COMP_CWORD > cmd_index + 1)); then
+        COMPREPLY=($(compgen -f -- "$cur"))
+        return
+    fi
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "@OPTIONS@" -- "$cur"))
+        return
+    fi
+    case "$cmd" in
+        "")
+            COMPREPLY=($(compgen -W "@COMMANDS@" -- "$cur"))
+            ;;
+        exec)
+            COMPREPLY=($(compgen -c -- "$cur"))
+            ;;
+@ACTIONS@    esac
+}
+
+complete -F _btcli btcli
+"#;
+
+fn bash() -> String {
+    let mut values = String::new();
+    for spec in OPTIONS.iter().filter(takes_value) {
+        let action = match option_values(spec) {
+            Values::Any => "return".to_string(),
+            Values::Files => r#"COMPREPLY=($(compgen -f -- "$cur"))"#.to_string(),
+            Values::Words(words) => {
+                let words: Vec<&str> = words.iter().map(|(word, _)| *word).collect();
+                format!(
+                    r#"COMPREPLY=($(compgen -W "{}" -- "$cur"))"#,
+                    words.join(" ")
+                )
+            }
+        };
+        values.push_str(&format!(
+            "        {})\n            {}\n            return\n            ;;\n",
+            option_names(spec).join("|"),
+            action
+        ));
+    }
+
+    let mut actions = String::new();
+    for spec in COMMANDS.iter().filter(|spec| !spec.actions.is_empty()) {
+        actions.push_str(&format!(
+            r#"        {})
+            if ((COMP_CWORD == cmd_index + 1)); then
+                COMPREPLY=($(compgen -W "{}" -- "$cur"))
+            else
+                COMPREPLY=($(compgen -f -- "$cur"))
+            fi
+            ;;
+"#,
+            spec.name,
+            spec.actions.join(" ")
+        ));
+    }
+
+    let takes_value: Vec<String> = OPTIONS
+        .iter()
+        .filter(takes_value)
+        .flat_map(option_names)
+        .collect();
+    let options: Vec<String> = OPTIONS.iter().flat_map(option_names).collect();
+    let commands: Vec<&str> = COMMANDS.iter().map(|spec| spec.name).collect();
+    BASH_TEMPLATE
+        .replace("@VALUES@", &values)
+        .replace("@TAKES_VALUE@", &takes_value.join("|"))
+        .replace("@OPTIONS@", &options.join(" "))
+        .replace("@COMMANDS@", &commands.join(" "))
+        .replace("@ACTIONS@", &actions)
+}
+
+const ZSH_TEMPLATE: &str = r#"#compdef btcli
+# btcli 的 zsh 补全脚本，由 `btcli completions zsh` 生成
+
+_btcli() {
+    local curcontext="$curcontext" state line
+    typeset -A opt_args
+
+    _arguments -C \
+@OPTIONS@        '1: :->command' \
+        '*:: :->args'
+
+    case $state in
+        command)
+            local -a commands
+            commands=(
+@COMMANDS@            )
+            _describe -t commands '命令' commands
+            ;;
+        args)
+            case $words[1] in
+                exec)
+                    shift words
+                    (( CURRENT-- ))
+                    _normal
+                    ;;
+@ACTIONS@            esac
+            ;;
+    esac
+}
+
+_btcli "$@"
+"#;
+
+/// 转义单引号字符串中的内容
+fn single_quoted(text: &str) -> String {
+    text.replace('\'', r"'\''")
+}
+
+/// 在 `specials` 中的字符前加反斜杠，用于 zsh `_arguments` 的说明和取值
+fn zsh_escape(text: &str, specials: &[char]) -> String {
+    let mut escaped = String::new();
+    for c in single_quoted(text).chars() {
+        if specials.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn zsh() -> String {
+    let mut options = String::new();
+    for spec in OPTIONS {
+        let names = option_names(spec);
+        let mut line = if names.len() > 1 {
+            format!("'({})'{{{}}}'", names.join(" "), names.join(","))
+        } else {
+            format!("'{}", names[0])
+        };
+        line.push_str(&format!("[{}]", zsh_escape(spec.help, &['[', ']'])));
+        let (separator, placeholder) = match spec.value {
+            ValueKind::Flag => ("", ""),
+            ValueKind::Required(placeholder) => (":", placeholder),
+            ValueKind::Optional(placeholder) => ("::", placeholder),
+        };
+        if !separator.is_empty() {
+            let action = match option_values(spec) {
+                Values::Any => " ".to_string(),
+                Values::Files => "_files".to_string(),
+                Values::Words(words) => {
+                    let escape = |text| zsh_escape(text, &[':', ' ', '(', ')']);
+                    let words: Vec<String> = words
+                        .iter()
+                        .map(|(word, help)| match help.is_empty() {
+                            true => escape(word),
+                            false => format!(r"{}\:{}", escape(word), escape(help)),
+                        })
+                        .collect();
+                    format!("(({}))", words.join(" "))
+                }
+            };
+            line.push_str(&format!("{}{}:{}", separator, placeholder, action));
+        }
+        options.push_str(&format!("        {}' \\\n", line));
+    }
+
+    let mut commands = String::new();
+    for spec in COMMANDS {
+        commands.push_str(&format!(
+            "                '{}:{}'\n",
+            spec.name,
+            zsh_escape(spec.help, &[':'])
+        ));
+    }
+
+    let mut actions = String::new();
+    for spec in COMMANDS.iter().filter(|spec| !spec.actions.is_empty()) {
+        actions.push_str(&format!(
+            "                {})
+                    if (( CURRENT == 2 )); then
+                        _values '操作' {}
+                    else
+                        _files
+                    fi
+                    ;;
+",
+            spec.name,
+            spec.actions.join(" ")
+        ));
+    }
+
+    ZSH_TEMPLATE
+        .replace("@OPTIONS@", &options)
+        .replace("@COMMANDS@", &commands)
+        .replace("@ACTIONS@", &actions)
+}
+
+/// 转义 fish 单引号字符串
+fn fish_quoted(text: &str) -> String {
+    format!("'{}'", text.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn fish() -> String {
+    let commands: Vec<&str> = COMMANDS.iter().map(|spec| spec.name).collect();
+    let mut script = format!(
+        "# btcli 的 fish 补全脚本，由 `btcli completions fish` 生成\n\nset -l commands {}\ncomplete -c btcli -f\n\n",
+        commands.join(" ")
+    );
+
+    for spec in OPTIONS {
+        let mut line = "complete -c btcli".to_string();
+        if let Some(short) = spec.short {
+            line.push_str(&format!(" -s {}", short));
+        }
+        line.push_str(&format!(" -l {}", spec.long));
+        if spec.value != ValueKind::Flag {
+            match option_values(spec) {
+                Values::Any => line.push_str(" -x"),
+                Values::Files => line.push_str(" -r -F"),
+                Values::Words(words) => {
+                    let words: Vec<String> = words
+                        .iter()
+                        .map(|(word, help)| match help.is_empty() {
+                            true => word.to_string(),
+                            false => format!(r"{}\t{}", word, fish_quoted(help)),
+                        })
+                        .collect();
+                    line.push_str(&format!(" -x -a \"{}\"", words.join(" ")));
+                }
+            }
+        }
+        line.push_str(&format!(" -d {}\n", fish_quoted(spec.help)));
+        script.push_str(&line);
+    }
+    script.push('\n');
+
+    for spec in COMMANDS {
+        script.push_str(&format!(
+            "complete -c btcli -n \"not __fish_seen_subcommand_from $commands\" -a {} -d {}\n",
+            spec.name,
+            fish_quoted(spec.help)
+        ));
+    }
+    for spec in COMMANDS.iter().filter(|spec| !spec.actions.is_empty()) {
+        let actions = spec.actions.join(" ");
+        script.push_str(&format!(
+            "complete -c btcli -n \"__fish_seen_subcommand_from {0}; and not __fish_seen_subcommand_from {1}\" -a '{1}'\n\
+             complete -c btcli -n \"__fish_seen_subcommand_from {0}; and __fish_seen_subcommand_from {1}\" -F\n",
+            spec.name, actions
+        ));
+    }
+    script.push_str(
+        "complete -c btcli -n \"__fish_seen_subcommand_from exec\" -F -a '(__fish_complete_command)'\n",
+    );
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_cover_definitions() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate(shell);
+            for spec in OPTIONS {
+                assert!(
+                    script.contains(spec.long),
+                    "{:?} 缺少 --{}",
+                    shell,
+                    spec.long
+                );
+            }
+            for spec in COMMANDS {
+                assert!(script.contains(spec.name), "{:?} 缺少 {}", shell, spec.name);
+            }
+            // 百度的非标准语种代码
+            assert!(script.contains("kor") && script.contains("fra"));
+        }
+        assert!(generate(Shell::Zsh).contains(r"kor\:韩语"));
+        assert!(generate(Shell::Fish).contains(r"kor\t'韩语'"));
+    }
+}
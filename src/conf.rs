@@ -13,35 +13,319 @@ pub struct AppConfig {
     pub source_lang: String,
     pub target_lang: String,
     pub enable_logging: bool,
+    /// 翻译引擎名称，默认为百度翻译
+    #[serde(default = "default_provider")]
+    pub provider: String,
+    /// 账户版本，决定默认的请求频率上限
+    #[serde(default)]
+    pub tier: AccountTier,
+    /// 手动指定每秒请求数，优先于 `tier`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qps: Option<f64>,
+    /// 长文本和批量任务的并发请求数，实际速率仍受 QPS 限制
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// 接口地址，不设置时使用百度翻译开放平台；可指向 btcli-mock 做离线测试
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_base: Option<String>,
+    /// 垂直领域（例如 it、finance、senimed），不设置时使用通用翻译
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// 暂时性错误的重试策略
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// 本地翻译缓存
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// 术语表
+    #[serde(default)]
+    pub glossary: GlossaryConfig,
+    /// 命令行翻译历史
+    #[serde(default)]
+    pub history: HistoryConfig,
+    /// 代理、超时与证书
+    #[serde(default)]
+    pub network: NetworkConfig,
+}
+
+/// 网络设置，对应配置文件中的 `[network]` 段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// 代理地址，支持 http://、https://、socks5://；不填时读取 HTTPS_PROXY 等环境变量，填空字符串则不使用代理
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// 建立连接的超时时间（秒），0 表示不限制
+    pub connect_timeout_secs: u64,
+    /// 等待响应的超时时间（秒），0 表示不限制
+    pub read_timeout_secs: u64,
+    /// PEM 格式的 CA 证书包，在内置根证书之外额外信任（用于企业代理的自签根证书）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_certs: Option<String>,
+    /// 自定义 User-Agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// 把请求与响应录制到该文件（appid、sign、salt 会被抹去）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<String>,
+    /// 从录制文件回放响应，不访问网络
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay: Option<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            ca_certs: None,
+            user_agent: None,
+            record: None,
+            replay: None,
+        }
+    }
+}
+
+/// 翻译历史设置，对应配置文件中的 `[history]` 段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// 默认关闭；开启后也只记录命令行直接给出的文本，不记录 -f 文件和标准输入的内容
+    pub enabled: bool,
+    /// 最多保留的条目数，0 表示不限制
+    pub max_entries: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 1000,
+        }
+    }
+}
+
+/// 术语表设置，对应配置文件中的 `[glossary]` 段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct GlossaryConfig {
+    /// TSV/CSV 术语表文件，每行“原文术语<TAB>译文术语”
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 使用百度账户中维护的术语库（needIntervene=1），而不是在本地替换术语
+    pub intervene: bool,
+}
+
+/// 翻译缓存设置，对应配置文件中的 `[cache]` 段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// 缓存有效期（秒），0 表示永不过期
+    pub ttl_secs: u64,
+    /// 最多保留的条目数，超出时淘汰最旧的条目
+    pub max_entries: usize,
+    /// 本次运行忽略已有缓存并重新请求（命令行 --refresh，不写入配置文件）
+    #[serde(skip)]
+    pub refresh: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 7 * 24 * 3600,
+            max_entries: 5000,
+            refresh: false,
+        }
+    }
+}
+
+/// 重试策略，对应配置文件中的 `[retry]` 段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最多尝试次数（含第一次），1 表示不重试
+    pub max_attempts: u32,
+    /// 指数退避的初始等待时间（毫秒）
+    pub base_delay_ms: u64,
+    /// 单次等待的上限（毫秒）
+    pub max_delay_ms: u64,
+    /// 54005 长 query 请求频繁时的等待时间（毫秒），百度要求 3 秒后再试
+    pub long_query_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 8000,
+            long_query_delay_ms: 3000,
+        }
+    }
+}
+
+/// 百度翻译账户版本
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountTier {
+    /// 标准版，1 QPS
+    #[default]
+    Standard,
+    /// 高级版，10 QPS
+    Advanced,
+    /// 尊享版，100 QPS
+    Premium,
+}
+
+impl AccountTier {
+    pub fn qps(self) -> f64 {
+        match self {
+            AccountTier::Standard => 1.0,
+            AccountTier::Advanced => 10.0,
+            AccountTier::Premium => 100.0,
+        }
+    }
+}
+
+impl AppConfig {
+    /// 校验语言设置，并把 ISO/BCP-47 写法（如 ja、zh-TW）规范为百度代码
+    pub fn normalize_langs(&mut self) -> Result<(), BtcliError> {
+        self.source_lang = crate::langs::normalize_source(&self.source_lang)?;
+        self.target_lang = crate::langs::normalize_target(&self.target_lang)?;
+        Ok(())
+    }
+
+    /// 实际生效的每秒请求数
+    pub fn effective_qps(&self) -> f64 {
+        match self.qps {
+            Some(qps) if qps > 0.0 => qps,
+            _ => self.tier.qps(),
+        }
+    }
+}
+
+fn default_provider() -> String {
+    "baidu".to_string()
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            appid: "your appid".to_string(),
+            key: "your key".to_string(),
+            source_lang: "auto".to_string(),
+            target_lang: "zh".to_string(),
+            enable_logging: false,
+            provider: default_provider(),
+            tier: AccountTier::default(),
+            qps: None,
+            concurrency: default_concurrency(),
+            api_base: None,
+            domain: None,
+            retry: RetryPolicy::default(),
+            cache: CacheConfig::default(),
+            glossary: GlossaryConfig::default(),
+            history: HistoryConfig::default(),
+            network: NetworkConfig::default(),
+        }
+    }
 }
 
 const EXAMPLE_CONF: &str = r#"
 appid = "your appid"
 key = "your key"
+# 语言可写百度代码或 ISO 639 / BCP-47 写法（ja、ko、zh-TW 等），完整列表见 btcli langs
 source_lang = "auto"
 target_lang = "zh"
 enable_logging = false
+provider = "baidu"
+# 账户版本: standard(1 QPS) / advanced(10 QPS) / premium(100 QPS)
+tier = "standard"
+# 长文本和批量翻译的并发请求数，实际速率仍受账户 QPS 限制
+concurrency = 4
+# 接口地址，离线测试时可指向 btcli-mock，例如 "http://127.0.0.1:8787"
+# api_base = "https://fanyi-api.baidu.com"
+# 垂直领域翻译: it / finance / machinery / senimed / novel / academic 等，不填则为通用翻译
+# domain = "it"
+
+[retry]
+max_attempts = 3
+base_delay_ms = 500
+max_delay_ms = 8000
+long_query_delay_ms = 3000
+
+[cache]
+enabled = true
+ttl_secs = 604800
+max_entries = 5000
+
+[glossary]
+# 术语表文件（TSV 或 CSV），相对路径按当前目录或 .btcli 目录查找
+# path = "glossary.tsv"
+# 为 true 时使用百度控制台中维护的术语库，不在本地替换术语
+intervene = false
+
+[history]
+# 翻译历史（原文与译文明文保存在 .btcli/history.jsonl），btcli history 查看，默认关闭
+# 开启后只记录命令行直接给出的文本和 repl 中的输入，不记录 -f 文件和标准输入的内容
+enabled = false
+max_entries = 1000
+
+[network]
+# 代理: http://host:port 或 socks5://host:port，不填时使用 HTTPS_PROXY 环境变量，填 "" 则禁用代理
+# proxy = "http://127.0.0.1:7890"
+connect_timeout_secs = 10
+read_timeout_secs = 30
+# PEM 格式的根证书包，企业代理使用自签证书时填写；在内置根证书之外额外信任
+# ca_certs = "corp-ca.pem"
+# user_agent = "btcli"
+# 录制请求与响应（appid、sign、salt 会被抹去），或从录制文件离线回放
+# record = "session.jsonl"
+# replay = "session.jsonl"
 "#;
 
 use std::fs::{create_dir_all, write};
 use std::path::PathBuf;
 
-// 获取配置文件路径
-fn get_config_path() -> PathBuf {
+/// 获取 .btcli 数据目录（与可执行文件同级），不存在时自动创建
+pub fn config_dir() -> PathBuf {
     let mut path = std::env::current_exe()
         .unwrap_or_else(|_| std::env::temp_dir().join("btcli"));
     path.pop(); // 移除可执行文件名
     path.push(".btcli");
     create_dir_all(&path).ok(); // 创建 .btcli 目录（如果不存在）
-    path.push("config.toml");
     path
 }
 
+/// 解析配置中引用的文件路径（术语表、CA 证书等），相对路径先按当前目录查找，再按 .btcli 目录查找
+pub fn resolve_path(path: &str) -> PathBuf {
+    let candidate = PathBuf::from(path);
+    if candidate.is_absolute() || candidate.exists() {
+        candidate
+    } else {
+        config_dir().join(path)
+    }
+}
+
+/// 获取配置文件路径
+pub fn get_config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
 fn create_conf() -> Result<(), std::io::Error> {
     let config_path = get_config_path();
     write(config_path, EXAMPLE_CONF)
 }
 
+use crate::error::BtcliError;
+use std::io::ErrorKind;
 use toml;
 
 pub fn save_conf_with_debug(
@@ -50,71 +334,61 @@ pub fn save_conf_with_debug(
     source_lang: &str,
     target_lang: &str,
     enable_logging: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), BtcliError> {
+    // 保留界面上没有的字段（例如 provider），只覆盖传入的部分；
+    // 配置文件无法解析时报错，不能用默认值覆盖用户的其他设置
+    let base = match load_file() {
+        Err(BtcliError::Io(e)) if e.kind() == ErrorKind::NotFound => AppConfig::default(),
+        other => other?,
+    };
     let conf = AppConfig {
         appid: appid.to_string(),
         key: key.to_string(),
         source_lang: source_lang.to_string(),
         target_lang: target_lang.to_string(),
         enable_logging,
+        ..base
     };
-    let conf_str = toml::to_string(&conf)?;
+    save_conf(&conf)
+}
+
+/// 把完整配置写入 config.toml
+pub fn save_conf(conf: &AppConfig) -> Result<(), BtcliError> {
+    let mut conf = conf.clone();
+    conf.normalize_langs()?;
+    let conf_str = toml::to_string(&conf)
+        .map_err(|e| BtcliError::Config(format!("Unable to serialize config: {}", e)))?;
     let config_path = get_config_path();
     write(config_path, conf_str)?;
     Ok(())
 }
 
-pub enum ConfigResult {
-    Ok(AppConfig),
-    Err(String),
-}
-
-fn load_file() -> ConfigResult {
+/// 读取并解析配置文件，文件不存在时返回 `ErrorKind::NotFound` 的 IO 错误
+fn load_file() -> Result<AppConfig, BtcliError> {
     let config_path = get_config_path();
-    if !config_path.exists() {
-        return ConfigResult::Err("config.toml not found, generating...".to_string());
-    } else if config_path.is_dir() {
-        return ConfigResult::Err(
+    if config_path.is_dir() {
+        return Err(BtcliError::Config(
             "config.toml is not a file, please delete it and try again.".to_string(),
-        );
+        ));
     }
 
-    let raw = std::fs::read_to_string(config_path);
-    let raw_c = match raw {
-        Ok(raw) => raw,
-        Err(e) => return ConfigResult::Err(format!(":( Unable to read config.toml: {}", e)),
-    };
-    let read_result = toml::from_str::<AppConfig>(&raw_c);
-    match read_result {
-        Ok(config) => ConfigResult::Ok(config),
-        Err(e) => ConfigResult::Err(format!(":( Unable to parse config.toml: {}", e)),
-    }
+    let raw_c = std::fs::read_to_string(config_path)?;
+    let mut conf = toml::from_str::<AppConfig>(&raw_c)
+        .map_err(|e| BtcliError::Config(format!("Unable to parse config.toml: {}", e)))?;
+    conf.normalize_langs()?;
+    Ok(conf)
 }
 
-pub fn try_init_conf() -> Result<AppConfig, String> {
+pub fn try_init_conf() -> Result<AppConfig, BtcliError> {
     match load_file() {
-        ConfigResult::Ok(config) => Ok(config),
-        ConfigResult::Err(e) => {
-            // 如果配置文件不存在，尝试创建示例配置文件
-            if e.contains("not found") {
-                match create_conf() {
-                    Ok(_) => {
-                        // 创建成功后，解析示例配置并返回
-                        match toml::from_str::<AppConfig>(EXAMPLE_CONF) {
-                            Ok(default_config) => Ok(default_config),
-                            Err(parse_err) => {
-                                Err(format!(":( Unable to parse example config: {}", parse_err))
-                            }
-                        }
-                    }
-                    Err(create_err) => {
-                        Err(format!(":( Unable to create config.toml: {}", create_err))
-                    }
-                }
-            } else {
-                // 其他错误情况，直接返回错误
-                Err(e)
-            }
+        // 如果配置文件不存在，尝试创建示例配置文件
+        Err(BtcliError::Io(e)) if e.kind() == ErrorKind::NotFound => {
+            create_conf()?;
+            // 创建成功后，解析示例配置并返回
+            toml::from_str::<AppConfig>(EXAMPLE_CONF)
+                .map_err(|e| BtcliError::Config(format!("Unable to parse example config: {}", e)))
         }
+        // 其他情况直接返回
+        other => other,
     }
-}
\ No newline at end of file
+}
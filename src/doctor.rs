@@ -0,0 +1,190 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 环境诊断（`btcli doctor`）
+//! 依次检查配置文件、凭据、网络设置、缓存目录、术语表和接口连通性，给出每一项的结论
+
+use crate::conf::AppConfig;
+use crate::error::{BtcliError, exit};
+use std::fmt;
+use std::time::Instant;
+
+/// 单项检查的结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// 不影响使用，但建议处理
+    Warn,
+    Fail,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    /// 失败时对应的退出码
+    pub exit_code: i32,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            exit_code: exit::OK,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status: Status::Warn,
+            ..Self::ok(name, detail)
+        }
+    }
+
+    fn fail(name: &'static str, error: &BtcliError) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            detail: error.to_string(),
+            exit_code: error.exit_code(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = match self.status {
+            Status::Ok => "[ OK ]",
+            Status::Warn => "[WARN]",
+            Status::Fail => "[FAIL]",
+        };
+        write!(f, "{} {}: {}", mark, self.name, self.detail)
+    }
+}
+
+/// 执行全部检查；配置无法加载时只返回这一项
+pub fn run() -> Vec<Check> {
+    let path = crate::conf::get_config_path();
+    let config = match crate::conf::try_init_conf() {
+        Ok(config) => config,
+        Err(e) => return vec![Check::fail("配置文件", &e)],
+    };
+    let mut checks = vec![Check::ok("配置文件", path.display().to_string())];
+    checks.push(check_credentials(&config));
+    checks.push(check_network(&config));
+    checks.push(check_cache(&config));
+    if let Some(check) = check_glossary(&config) {
+        checks.push(check);
+    }
+    // 凭据和网络设置有问题时不再发请求
+    if checks.iter().all(|check| check.status != Status::Fail) {
+        checks.push(check_connectivity(&config));
+    }
+    checks
+}
+
+/// 第一个失败项的退出码，全部通过时为 0
+pub fn exit_code(checks: &[Check]) -> i32 {
+    checks
+        .iter()
+        .find(|check| check.status == Status::Fail)
+        .map_or(exit::OK, |check| check.exit_code)
+}
+
+fn check_credentials(config: &AppConfig) -> Check {
+    let defaults = AppConfig::default();
+    if config.appid.trim().is_empty() || config.appid == defaults.appid {
+        return Check::fail(
+            "凭据",
+            &BtcliError::Config("appid is not set in config.toml".to_string()),
+        );
+    }
+    if config.key.trim().is_empty() || config.key == defaults.key {
+        return Check::fail(
+            "凭据",
+            &BtcliError::Config("key is not set in config.toml".to_string()),
+        );
+    }
+    Check::ok(
+        "凭据",
+        format!(
+            "appid {}，{:?} 版（{} QPS）",
+            config.appid,
+            config.tier,
+            config.effective_qps()
+        ),
+    )
+}
+
+fn check_network(config: &AppConfig) -> Check {
+    let network = &config.network;
+    if let Err(e) = crate::http::agent(network) {
+        return Check::fail("网络设置", &e);
+    }
+    let proxy = match network.proxy.as_deref() {
+        Some("") => "不使用代理".to_string(),
+        Some(proxy) => format!("代理 {}", proxy),
+        None => "代理取自环境变量".to_string(),
+    };
+    let base = config
+        .api_base
+        .as_deref()
+        .unwrap_or(crate::fycore::DEFAULT_API_BASE);
+    Check::ok("网络设置", format!("{}，{}", base, proxy))
+}
+
+fn check_cache(config: &AppConfig) -> Check {
+    if !config.cache.enabled {
+        return Check::warn("缓存", "已关闭");
+    }
+    let cache = crate::cache::Cache::open(&config.cache);
+    let probe = cache.dir().join(".doctor");
+    let writable = std::fs::create_dir_all(cache.dir())
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match writable {
+        Ok(()) => Check::ok("缓存", cache.dir().display().to_string()),
+        Err(e) => Check::warn("缓存", format!("{} 不可写: {}", cache.dir().display(), e)),
+    }
+}
+
+fn check_glossary(config: &AppConfig) -> Option<Check> {
+    let path = config.glossary.path.as_deref().filter(|p| !p.is_empty())?;
+    let path = crate::conf::resolve_path(path);
+    Some(match crate::glossary::Glossary::load(&path) {
+        Ok(glossary) => Check::ok(
+            "术语表",
+            format!("{}（{} 条术语）", path.display(), glossary.terms().len()),
+        ),
+        Err(e) => Check::fail("术语表", &e),
+    })
+}
+
+/// 发送一次真实的翻译请求，不读写缓存
+fn check_connectivity(config: &AppConfig) -> Check {
+    let mut config = config.clone();
+    config.cache.enabled = false;
+    let translator = match crate::translator::from_config(&config) {
+        Ok(translator) => translator,
+        Err(e) => return Check::fail("接口连通性", &e),
+    };
+    let started = Instant::now();
+    match translator.translate("en", "zh", "hello") {
+        Ok(result) => Check::ok(
+            "接口连通性",
+            format!(
+                "hello -> {}（{} ms）",
+                result.text(),
+                started.elapsed().as_millis()
+            ),
+        ),
+        Err(e) => Check::fail("接口连通性", &e),
+    }
+}
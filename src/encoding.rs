@@ -0,0 +1,180 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 文本文件编码识别与转换
+//! 读取时按 BOM、UTF-8 合法性、UTF-16 零字节分布依次判断，其余按 GB18030（兼容 GBK/GB2312）解码；
+//! 写出时默认 UTF-8，也可以指定其他编码
+
+use crate::error::BtcliError;
+use std::fmt;
+
+/// 支持的文本编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// 带 BOM 的 UTF-8
+    Utf8Bom,
+    Gbk,
+    Gb18030,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// `--encoding` 接受的规范名称，用于补全
+    pub const NAMES: &[&str] = &[
+        "utf-8",
+        "utf-8-bom",
+        "gbk",
+        "gb18030",
+        "utf-16le",
+        "utf-16be",
+    ];
+
+    /// 解析命令行或配置中的编码名，不区分大小写
+    pub fn parse(name: &str) -> Result<Self, BtcliError> {
+        let normalized = name.trim().to_ascii_lowercase().replace('_', "-");
+        match normalized.as_str() {
+            "utf-8" | "utf8" => Ok(Self::Utf8),
+            "utf-8-bom" | "utf8-bom" | "utf-8-sig" => Ok(Self::Utf8Bom),
+            "gbk" | "gb2312" | "cp936" => Ok(Self::Gbk),
+            "gb18030" => Ok(Self::Gb18030),
+            "utf-16" | "utf16" | "utf-16le" | "utf16le" => Ok(Self::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(Self::Utf16Be),
+            _ => Err(BtcliError::Config(format!(
+                "Unsupported encoding: {} (expected utf-8, utf-8-bom, gbk, gb18030, utf-16le or utf-16be)",
+                name
+            ))),
+        }
+    }
+
+    /// 识别字节内容的编码
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            return Self::Utf8Bom;
+        }
+        if bytes.starts_with(&[0xFF, 0xFE]) {
+            return Self::Utf16Le;
+        }
+        if bytes.starts_with(&[0xFE, 0xFF]) {
+            return Self::Utf16Be;
+        }
+        if let Some(utf16) = guess_utf16(bytes) {
+            return utf16;
+        }
+        if std::str::from_utf8(bytes).is_ok() {
+            return Self::Utf8;
+        }
+        Self::Gb18030
+    }
+
+    /// 按本编码解码，BOM 会被去掉，无法解码的字节替换为 U+FFFD
+    pub fn decode(self, bytes: &[u8]) -> String {
+        let (encoding, body) = match self {
+            Self::Utf8 | Self::Utf8Bom => (
+                encoding_rs::UTF_8,
+                bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes),
+            ),
+            Self::Gbk | Self::Gb18030 => (encoding_rs::GB18030, bytes),
+            Self::Utf16Le => (
+                encoding_rs::UTF_16LE,
+                bytes.strip_prefix(&[0xFF, 0xFE]).unwrap_or(bytes),
+            ),
+            Self::Utf16Be => (
+                encoding_rs::UTF_16BE,
+                bytes.strip_prefix(&[0xFE, 0xFF]).unwrap_or(bytes),
+            ),
+        };
+        encoding.decode_without_bom_handling(body).0.into_owned()
+    }
+
+    /// 按本编码编码，UTF-16 和 UTF-8 BOM 会写入 BOM
+    ///
+    /// GBK 无法表示的字符会被写成 HTML 数字字符引用（`&#...;`）
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Utf8Bom => [&[0xEF, 0xBB, 0xBF], text.as_bytes()].concat(),
+            Self::Gbk => encoding_rs::GBK.encode(text).0.into_owned(),
+            Self::Gb18030 => encoding_rs::GB18030.encode(text).0.into_owned(),
+            // encoding_rs 不提供 UTF-16 编码器
+            Self::Utf16Le => [0xFEFF]
+                .into_iter()
+                .chain(text.encode_utf16())
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+            Self::Utf16Be => [0xFEFF]
+                .into_iter()
+                .chain(text.encode_utf16())
+                .flat_map(u16::to_be_bytes)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 (BOM)",
+            Self::Gbk => "GBK",
+            Self::Gb18030 => "GB18030",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 没有 BOM 的 UTF-16：大部分字符的高字节或低字节为零
+fn guess_utf16(bytes: &[u8]) -> Option<TextEncoding> {
+    if bytes.len() < 4 || !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let even_zeros = bytes.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_zeros = bytes.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    // 至少四成字符是 ASCII，且另一侧几乎没有零字节
+    if odd_zeros * 10 >= pairs * 4 && even_zeros * 10 < pairs {
+        Some(TextEncoding::Utf16Le)
+    } else if even_zeros * 10 >= pairs * 4 && odd_zeros * 10 < pairs {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// 读取文本文件并自动识别编码
+pub fn read_file(path: &str) -> Result<(String, TextEncoding), BtcliError> {
+    let bytes = std::fs::read(path)?;
+    let encoding = TextEncoding::detect(&bytes);
+    Ok((encoding.decode(&bytes), encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_decode_gbk() {
+        let bytes = encoding_rs::GBK.encode("你好，世界").0.into_owned();
+        let encoding = TextEncoding::detect(&bytes);
+        assert_eq!(encoding, TextEncoding::Gb18030);
+        assert_eq!(encoding.decode(&bytes), "你好，世界");
+    }
+
+    #[test]
+    fn test_utf16_round_trip() {
+        for encoding in [TextEncoding::Utf16Le, TextEncoding::Utf16Be] {
+            let bytes = encoding.encode("Hello 你好");
+            assert_eq!(TextEncoding::detect(&bytes), encoding);
+            assert_eq!(encoding.decode(&bytes), "Hello 你好");
+        }
+        let bom = TextEncoding::Utf8Bom.encode("abc");
+        assert_eq!(TextEncoding::detect(&bom), TextEncoding::Utf8Bom);
+        assert_eq!(TextEncoding::detect(b"plain"), TextEncoding::Utf8);
+    }
+}
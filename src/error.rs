@@ -0,0 +1,232 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 统一错误类型
+//! 库的使用者可以按变体匹配失败原因，CLI 和 TUI 也据此区分处理方式
+
+use crate::fyerrcodes::query_msg;
+use std::fmt;
+
+/// 命令行的进程退出码，脚本可据此区分失败原因
+pub mod exit {
+    /// 成功
+    pub const OK: i32 = 0;
+    /// 其他错误（响应无法解析、文件读写失败等）
+    pub const FAILURE: i32 = 1;
+    /// 命令行用法错误：未知选项、缺少参数、没有提供原文
+    pub const USAGE: i32 = 2;
+    /// 配置文件缺失或不合法
+    pub const CONFIG: i32 = 3;
+    /// 网络错误、HTTP 错误或服务端超时/系统错误（52001/52002）
+    pub const NETWORK: i32 = 4;
+    /// 身份认证失败（52003 appid 错误、54001 签名错误、IP 或服务未开通等）
+    pub const AUTH: i32 = 5;
+    /// 账户余额不足（54004）
+    pub const QUOTA: i32 = 6;
+    /// 访问频率受限（54003/54005）
+    pub const RATE_LIMIT: i32 = 7;
+    /// 内容被拒绝翻译（20003）
+    pub const CONTENT: i32 = 8;
+    /// 不支持的语种（58001）
+    pub const UNSUPPORTED_LANG: i32 = 9;
+    /// 回放模式下磁带中没有匹配的录制
+    pub const REPLAY_MISS: i32 = 10;
+    /// 被用户取消
+    pub const CANCELLED: i32 = 130;
+}
+
+#[derive(Debug)]
+pub enum BtcliError {
+    /// 网络连接失败（DNS、连接被拒绝、超时等）
+    Network(String),
+    /// 服务端返回了非 2xx 的 HTTP 状态码
+    Http { status: u16 },
+    /// 翻译接口返回的业务错误，message 取自 `fyerrcodes`
+    Api { code: usize, message: String },
+    /// 响应内容无法解析
+    Parse(String),
+    /// 配置缺失或不合法
+    Config(String),
+    /// 本地文件读写失败
+    Io(std::io::Error),
+    /// 回放模式下磁带中没有匹配的录制
+    ReplayMiss(String),
+    /// 调用方的请求不完整或不合法（例如本地服务缺少参数）
+    BadRequest(String),
+    /// 任务被用户取消
+    Cancelled,
+}
+
+impl BtcliError {
+    /// 根据错误码构造接口错误，未收录的错误码使用服务端给出的描述
+    pub fn api(code: usize, server_msg: &str) -> Self {
+        let known = query_msg(code);
+        let message = if known == "未知错误" && !server_msg.is_empty() {
+            server_msg.to_string()
+        } else {
+            known.to_string()
+        };
+        BtcliError::Api { code, message }
+    }
+
+    /// 接口错误码，非接口错误返回 None
+    pub fn code(&self) -> Option<usize> {
+        match self {
+            BtcliError::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// 错误类别的英文标识，供机器可读的输出使用
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BtcliError::Network(_) => "network",
+            BtcliError::Http { .. } => "http",
+            BtcliError::Api { .. } => "api",
+            BtcliError::Parse(_) => "parse",
+            BtcliError::Config(_) => "config",
+            BtcliError::Io(_) => "io",
+            BtcliError::ReplayMiss(_) => "replay_miss",
+            BtcliError::BadRequest(_) => "bad_request",
+            BtcliError::Cancelled => "cancelled",
+        }
+    }
+
+    /// 对应的进程退出码，见 [`exit`]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BtcliError::Api { code, .. } => match code {
+                54004 => exit::QUOTA,
+                54003 | 54005 => exit::RATE_LIMIT,
+                20003 => exit::CONTENT,
+                58001 => exit::UNSUPPORTED_LANG,
+                52001 | 52002 => exit::NETWORK,
+                _ if self.is_auth() => exit::AUTH,
+                _ => exit::FAILURE,
+            },
+            BtcliError::Http { status: 429 } => exit::RATE_LIMIT,
+            BtcliError::Http { .. } if self.is_auth() => exit::AUTH,
+            BtcliError::Network(_) | BtcliError::Http { .. } => exit::NETWORK,
+            BtcliError::Config(_) => exit::CONFIG,
+            BtcliError::Parse(_) | BtcliError::Io(_) => exit::FAILURE,
+            BtcliError::ReplayMiss(_) => exit::REPLAY_MISS,
+            BtcliError::BadRequest(_) => exit::USAGE,
+            BtcliError::Cancelled => exit::CANCELLED,
+        }
+    }
+
+    /// 稍后重试可能成功的错误：网络问题、5xx 以及百度的超时/系统错误/限流
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BtcliError::Network(_) => true,
+            BtcliError::Http { status } => *status == 429 || *status >= 500,
+            BtcliError::Api { code, .. } => matches!(code, 52001 | 52002 | 54003 | 54005),
+            _ => false,
+        }
+    }
+
+    /// 身份认证类错误：appid、签名、IP 或认证状态有问题
+    pub fn is_auth(&self) -> bool {
+        match self {
+            BtcliError::Http { status } => *status == 401 || *status == 403,
+            BtcliError::Api { code, .. } => {
+                matches!(code, 52003 | 54001 | 58000 | 58002 | 58003 | 90107)
+            }
+            _ => false,
+        }
+    }
+
+    /// 额度类错误：余额不足或访问频率受限
+    pub fn is_quota(&self) -> bool {
+        matches!(
+            self,
+            BtcliError::Api {
+                // 54003 访问频率受限，54004 余额不足，54005 长 query 请求频繁
+                code: 54003..=54005,
+                ..
+            }
+        )
+    }
+
+    /// 给用户的处理建议，CLI 和 TUI 在错误信息后附加显示
+    pub fn hint(&self) -> Option<&'static str> {
+        if self.is_auth() {
+            Some("请检查配置中的 appid 和 key 是否正确，以及服务是否已开通")
+        } else if self.is_quota() {
+            Some("额度或频率受限，请稍后再试或前往管理控制台查看账户状态")
+        } else if self.is_retryable() {
+            Some("这可能是暂时性的问题，请稍后重试")
+        } else if matches!(self, BtcliError::ReplayMiss(_)) {
+            Some("请重新录制，或检查语言、领域和术语设置是否与录制时一致")
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for BtcliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BtcliError::Network(msg) => write!(f, ":( error sending request: {}", msg),
+            BtcliError::Http { status } => write!(f, ":( Server responded with HTTP {}", status),
+            BtcliError::Api { code, message } => {
+                write!(f, ":( We asked, but server said: {} ({})", message, code)
+            }
+            BtcliError::Parse(msg) => write!(f, ":( {}", msg),
+            BtcliError::Config(msg) => write!(f, ":( {}", msg),
+            BtcliError::Io(e) => write!(f, ":( IO error: {}", e),
+            BtcliError::ReplayMiss(msg) => write!(f, ":( {}", msg),
+            BtcliError::BadRequest(msg) => write!(f, ":( {}", msg),
+            BtcliError::Cancelled => write!(f, ":( Translation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for BtcliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BtcliError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for BtcliError {
+    fn from(e: std::io::Error) -> Self {
+        BtcliError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for BtcliError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::StatusCode(status) => BtcliError::Http { status },
+            ureq::Error::Io(e) => BtcliError::Network(e.to_string()),
+            other => BtcliError::Network(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_by_error_class() {
+        assert_eq!(BtcliError::api(52003, "").exit_code(), exit::AUTH);
+        assert_eq!(BtcliError::api(54001, "").exit_code(), exit::AUTH);
+        assert_eq!(BtcliError::api(54004, "").exit_code(), exit::QUOTA);
+        assert_eq!(BtcliError::api(54003, "").exit_code(), exit::RATE_LIMIT);
+        assert_eq!(BtcliError::api(20003, "").exit_code(), exit::CONTENT);
+        assert_eq!(
+            BtcliError::api(58001, "").exit_code(),
+            exit::UNSUPPORTED_LANG
+        );
+        assert_eq!(BtcliError::Network("x".into()).exit_code(), exit::NETWORK);
+        assert_eq!(BtcliError::Config("x".into()).exit_code(), exit::CONFIG);
+        assert_eq!(BtcliError::BadRequest("x".into()).exit_code(), exit::USAGE);
+    }
+}
@@ -6,24 +6,13 @@
 
 //! Help information module for btcli
 //! Contains language lists, error codes and special messages
+//! The language table is generated from the registry in `langs`
 
 use crate::fancy_egg::get_random_blessing;
 
-const HELP_INFO: &str = r#"=== btcli - 百度翻译命令行工具帮助信息 ===
-
-【常见语种列表】
-名称        代码    名称        代码    名称        代码
-自动检测    auto   中文        zh      英语         en
-粤语        yue    文言文      wyw     日语         jp
-韩语        kor    法语        fra     西班牙语     spa
-泰语        th     阿拉伯语     ara     俄语        ru
-葡萄牙语     pt     德语        de      意大利语     it
-希腊语      el     荷兰语       nl      波兰语       pl
-保加利亚语   bul    爱沙尼亚语    est     丹麦语      dan
-芬兰语      fin    捷克语       cs      罁马尼亚语    rom
-斯洛文尼亚语 slo    瑞典语       swe     匈牙利语      hu
-繁体中文    cht    越南语       vie
+const HELP_HEADER: &str = "=== btcli - 百度翻译命令行工具帮助信息 ===\n\n【常见语种列表】\n";
 
+const HELP_INFO: &str = r#"
 【错误码列表】
 错误码    含义              解决方案
 52000    成功              
@@ -52,7 +41,13 @@ const HELP_INFO: &str = r#"=== btcli - 百度翻译命令行工具帮助信息 =
 /// 获取帮助信息
 pub fn get_help_info() -> String {
     let blessing = get_random_blessing();
-    format!("{}\n{}", HELP_INFO, &blessing)
+    format!(
+        "{}{}{}\n{}",
+        HELP_HEADER,
+        crate::langs::help_table(),
+        HELP_INFO,
+        &blessing
+    )
 }
 
 #[cfg(test)]
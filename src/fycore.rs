@@ -4,151 +4,736 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::cache::Cache;
+use crate::cassette::{self, RequestKey};
+use crate::chunker;
 use crate::conf::AppConfig;
+use crate::error::BtcliError;
 use crate::fancy_egg::{EGG_CODE, decrypt};
-use crate::fyerrcodes::query_msg;
+use crate::glossary::{Glossary, Masked};
+use crate::http;
+use crate::pool::{self, JobControl};
+use crate::ratelimit;
+use crate::retry::with_retry_until;
+use crate::translator::{
+    Capabilities, DictEntry, DictPart, Phonetic, Translation, TranslationItem, Translator,
+};
 use md5;
 use rand;
 use serde::Deserialize;
 use serde_json;
-use std::collections::HashMap;
 use std::str::FromStr;
-use ureq;
 
-/// 提高程序可读性，避免硬编码host
-const HOST: &str = "https://fanyi-api.baidu.com/api/trans/vip/translate";
+/// 默认的百度翻译开放平台地址，可通过配置中的 `api_base` 替换（例如指向 btcli-mock）
+pub const DEFAULT_API_BASE: &str = "https://fanyi-api.baidu.com";
+/// 通用翻译接口
+pub const TRANSLATE_PATH: &str = "/api/trans/vip/translate";
+/// 垂直领域翻译接口
+pub const FIELD_PATH: &str = "/api/trans/vip/fieldtranslate";
+/// 语种识别接口
+pub const DETECT_PATH: &str = "/api/trans/vip/language";
+
+/// 百度垂直领域翻译支持的领域代码及名称
+pub const BAIDU_DOMAINS: [(&str, &str); 11] = [
+    ("it", "信息技术"),
+    ("finance", "金融财经"),
+    ("machinery", "机械制造"),
+    ("senimed", "生物医药"),
+    ("novel", "网络文学"),
+    ("academic", "学术论文"),
+    ("aerospace", "航空航天"),
+    ("wiki", "人文社科"),
+    ("news", "新闻资讯"),
+    ("law", "法律法规"),
+    ("contract", "合同"),
+];
+
+/// 判断领域代码是否受支持
+pub fn is_valid_domain(domain: &str) -> bool {
+    BAIDU_DOMAINS.iter().any(|(code, _)| *code == domain)
+}
+
+/// 百度单次请求 q 的最大字节数
+const MAX_QUERY_BYTES: usize = 6000;
 
 /// 定义响应体数据结构
 // 成功
 #[derive(Deserialize, Debug)]
-#[allow(dead_code)]
 struct TranslationResponse {
     from: String,
     to: String,
-    trans_result: Vec<TranslationItem>,
+    trans_result: Vec<RawTranslationItem>,
 }
 
+// 开启 dict/tts 时每个片段会多出发音链接，dict 是一段 JSON 字符串
 #[derive(Deserialize, Debug)]
-#[allow(dead_code)]
-struct TranslationItem {
+struct RawTranslationItem {
     src: String,
     dst: String,
+    #[serde(default)]
+    src_tts: Option<String>,
+    #[serde(default)]
+    dst_tts: Option<String>,
+    #[serde(default)]
+    dict: Option<String>,
+}
+
+impl From<RawTranslationItem> for TranslationItem {
+    fn from(raw: RawTranslationItem) -> Self {
+        let dict = raw.dict.as_deref().and_then(parse_dict);
+        TranslationItem {
+            src: raw.src,
+            dst: raw.dst,
+            src_tts: raw.src_tts.filter(|url| !url.is_empty()),
+            dst_tts: raw.dst_tts.filter(|url| !url.is_empty()),
+            dict,
+        }
+    }
 }
 
 //失败
 #[derive(Deserialize, Debug)]
-#[allow(dead_code)]
 struct ErrorResponse {
     error_code: serde_json::Value,
     error_msg: String,
 }
 
-fn calculate_sign(appid: &str, q: &str, salt: &str, key: &str) -> String {
+// 语种识别，成功时 error_code 为 0
+#[derive(Deserialize, Debug)]
+struct DetectResponse {
+    error_code: serde_json::Value,
+    #[serde(default)]
+    error_msg: String,
+    data: Option<DetectData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DetectData {
+    src: String,
+}
+
+pub(crate) fn calculate_sign(appid: &str, q: &str, salt: &str, key: &str) -> String {
     let sign_str = format!("{}{}{}{}", appid, q, salt, key);
     format!("{:x}", md5::compute(sign_str.as_bytes()))
 }
 
+/// 拼接接口完整地址
+fn endpoint(app_config: &AppConfig, path: &str) -> String {
+    let base = app_config
+        .api_base
+        .as_deref()
+        .filter(|base| !base.is_empty())
+        .unwrap_or(DEFAULT_API_BASE);
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+/// 配置中实际生效的领域，空字符串视为未设置
+fn active_domain(app_config: &AppConfig) -> Option<&str> {
+    app_config
+        .domain
+        .as_deref()
+        .filter(|domain| !domain.is_empty())
+}
+
+/// 领域翻译的签名在 salt 和密钥之间多了 domain
+pub(crate) fn calculate_field_sign(
+    appid: &str,
+    q: &str,
+    salt: &str,
+    domain: &str,
+    key: &str,
+) -> String {
+    let sign_str = format!("{}{}{}{}{}", appid, q, salt, domain, key);
+    format!("{:x}", md5::compute(sign_str.as_bytes()))
+}
+
 fn send_response(
     appid: &str,
     from: &str,
     to: &str,
     q: &str,
     app_config: &AppConfig,
-) -> Result<String, Box<dyn std::error::Error>> {
+    with_dict: bool,
+) -> Result<String, BtcliError> {
     let salt = rand::random::<u32>().to_string();
-    let sign = calculate_sign(appid, q, &salt, &app_config.key);
 
-    let mut params = HashMap::new();
-    params.insert("appid", appid);
-    params.insert("from", from);
-    params.insert("to", to);
-    params.insert("q", q);
-    params.insert("salt", &salt);
-    params.insert("sign", &sign);
+    let mut params = vec![
+        ("appid", appid),
+        ("from", from),
+        ("to", to),
+        ("q", q),
+        ("salt", salt.as_str()),
+    ];
+
+    // 配置了领域时改用领域翻译接口及对应的签名
+    match active_domain(app_config) {
+        Some(domain) => {
+            let sign = calculate_field_sign(appid, q, &salt, domain, &app_config.key);
+            params.push(("domain", domain));
+            params.push(("sign", &sign));
+            let provider = format!("baidu:field:{}", domain);
+            exchange(app_config, &provider, from, to, q, FIELD_PATH, &params)
+        }
+        None => {
+            let sign = calculate_sign(appid, q, &salt, &app_config.key);
+            params.push(("sign", &sign));
+            // 术语干预、词典和发音参数都不参与签名
+            if app_config.glossary.intervene {
+                params.push(("needIntervene", "1"));
+            }
+            let mut provider = "baidu".to_string();
+            if app_config.glossary.intervene {
+                provider.push_str(":intervene");
+            }
+            if with_dict {
+                params.push(("dict", "1"));
+                params.push(("tts", "1"));
+                provider.push_str(":dict");
+            }
+            exchange(app_config, &provider, from, to, q, TRANSLATE_PATH, &params)
+        }
+    }
+}
+
+fn send_detect(appid: &str, q: &str, app_config: &AppConfig) -> Result<String, BtcliError> {
+    let salt = rand::random::<u32>().to_string();
+    // 语种识别与通用翻译使用相同的签名方式
+    let sign = calculate_sign(appid, q, &salt, &app_config.key);
 
-    let response = ureq::post(HOST)
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .send_form(&params)?;
+    let params = [
+        ("appid", appid),
+        ("q", q),
+        ("salt", salt.as_str()),
+        ("sign", sign.as_str()),
+    ];
+    exchange(app_config, "baidu:detect", "", "", q, DETECT_PATH, &params)
+}
 
-    let response_text = response.into_body().read_to_string()?;
-    Ok(response_text)
+/// 发送请求，录制或回放模式下经由磁带文件
+fn exchange(
+    app_config: &AppConfig,
+    provider: &str,
+    from: &str,
+    to: &str,
+    q: &str,
+    path: &str,
+    params: &[(&str, &str)],
+) -> Result<String, BtcliError> {
+    let url = endpoint(app_config, path);
+    let key = RequestKey {
+        provider: provider.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        q: q.to_string(),
+    };
+    cassette::exchange(&app_config.network, key, &url, params, || {
+        http::post_form(&app_config.network, &url, params)
+    })
 }
 
 ///已经确定成功，从body里获取返回结果
-fn patch_raw(content: String) -> Result<String, String> {
+fn patch_raw(content: String) -> Result<Translation, BtcliError> {
     let parsed_response = serde_json::from_str::<TranslationResponse>(&content)
-        .map_err(|e| format!(":( Failed to parse successful response: {}", e))?;
+        .map_err(|e| BtcliError::Parse(format!("Failed to parse successful response: {}", e)))?;
 
     if !parsed_response.trans_result.is_empty() {
-        Ok(parsed_response.trans_result[0].dst.clone())
+        Ok(Translation {
+            from: parsed_response.from,
+            to: parsed_response.to,
+            items: parsed_response
+                .trans_result
+                .into_iter()
+                .map(TranslationItem::from)
+                .collect(),
+            cached: false,
+        })
     } else {
-        Err(":( Response contains no translation results".to_string())
+        Err(BtcliError::Parse(
+            "Response contains no translation results".to_string(),
+        ))
+    }
+}
+
+/// 解析百度 dict 字段中的 simple_means 部分，结构不符时返回 None
+fn parse_dict(raw: &str) -> Option<DictEntry> {
+    let value: serde_json::Value = serde_json::from_str(raw).ok()?;
+    let simple = value.pointer("/word_result/simple_means")?;
+    let word = simple
+        .get("word_name")
+        .and_then(|w| w.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut phonetics = Vec::new();
+    let mut parts = Vec::new();
+    let symbols = simple.get("symbols").and_then(|s| s.as_array());
+    if let Some(symbol) = symbols.and_then(|symbols| symbols.first()) {
+        for (field, label) in [("ph_en", "英"), ("ph_am", "美"), ("word_symbol", "拼音")] {
+            if let Some(ph) = symbol.get(field).and_then(|p| p.as_str())
+                && !ph.is_empty()
+            {
+                phonetics.push(Phonetic {
+                    label: label.to_string(),
+                    value: ph.to_string(),
+                });
+            }
+        }
+        for part in symbol
+            .get("parts")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            // 英文词条的释义是字符串，中文词条的释义是 {"text": ...} 对象
+            let means: Vec<String> = part
+                .get("means")
+                .and_then(|m| m.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|mean| {
+                    mean.as_str()
+                        .or_else(|| mean.get("text").and_then(|t| t.as_str()))
+                        .map(|text| text.to_string())
+                })
+                .collect();
+            if means.is_empty() {
+                continue;
+            }
+            let part_name = part
+                .get("part")
+                .or_else(|| part.get("part_name"))
+                .and_then(|p| p.as_str())
+                .unwrap_or_default();
+            parts.push(DictPart {
+                part: part_name.to_string(),
+                means,
+            });
+        }
+    }
+
+    if word.is_empty() && parts.is_empty() {
+        None
+    } else {
+        Some(DictEntry {
+            word,
+            phonetics,
+            parts,
+        })
     }
 }
 
 ///解析错误响应
-fn patch_error(body_content: String) -> Result<ErrorResponse, String> {
-    serde_json::from_str::<ErrorResponse>(&body_content)
-        .map_err(|e| format!(":( Failed to parse error response: {}", e))
+fn patch_error(body_content: String) -> BtcliError {
+    let err_resp = match serde_json::from_str::<ErrorResponse>(&body_content) {
+        Ok(err_resp) => err_resp,
+        Err(e) => return BtcliError::Parse(format!("Failed to parse error response: {}", e)),
+    };
+
+    match parse_error_code(&err_resp.error_code) {
+        Some(code) => BtcliError::api(code, &err_resp.error_msg),
+        None => BtcliError::Parse("failed to parse error code".to_string()),
+    }
+}
+
+/// 处理 error_code 可能是字符串或数字的情况
+fn parse_error_code(value: &serde_json::Value) -> Option<usize> {
+    match value.as_u64() {
+        Some(code) => Some(code as usize),
+        None => value
+            .as_str()
+            .and_then(|code_str| usize::from_str(code_str).ok()),
+    }
+}
+
+///解析语种识别响应
+fn patch_detect(body_content: String) -> Result<String, BtcliError> {
+    let resp = serde_json::from_str::<DetectResponse>(&body_content)
+        .map_err(|e| BtcliError::Parse(format!("Failed to parse detect response: {}", e)))?;
+
+    match parse_error_code(&resp.error_code) {
+        Some(0) => resp
+            .data
+            .map(|data| data.src)
+            .ok_or_else(|| BtcliError::Parse("Detect response contains no language".to_string())),
+        Some(code) => Err(BtcliError::api(code, &resp.error_msg)),
+        None => Err(BtcliError::Parse("failed to parse error code".to_string())),
+    }
+}
+
+/// 百度翻译引擎
+pub struct BaiduTranslator {
+    config: AppConfig,
+}
+
+impl BaiduTranslator {
+    pub fn new(config: AppConfig) -> Self {
+        Self { config }
+    }
+
+    /// 需要在本地替换的术语表；开启术语干预或未配置术语表时返回 None
+    fn local_glossary(&self) -> Result<Option<Glossary>, BtcliError> {
+        if self.config.glossary.intervene {
+            return Ok(None);
+        }
+        match self.config.glossary.path.as_deref() {
+            Some(path) if !path.is_empty() => {
+                let glossary = Glossary::load(&crate::conf::resolve_path(path))?;
+                Ok(Some(glossary).filter(|glossary| !glossary.is_empty()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 缓存键中的引擎标识，领域和术语都会影响译文，一并计入
+    fn cache_provider(&self, glossary: Option<&Glossary>) -> String {
+        let mut provider = self.name().to_string();
+        if let Some(domain) = active_domain(&self.config) {
+            provider.push_str(&format!(":{}", domain));
+        }
+        if self.config.glossary.intervene {
+            provider.push_str(":intervene");
+        } else if let Some(glossary) = glossary {
+            provider.push_str(&format!(":{}", glossary.fingerprint()));
+        }
+        provider
+    }
+
+    /// 限流并按策略重试地执行一次接口调用，等待令牌和退避期间响应取消
+    fn call<T, F>(&self, control: &JobControl, mut op: F) -> Result<T, BtcliError>
+    where
+        F: FnMut() -> Result<T, BtcliError>,
+    {
+        // 同一账户共享令牌桶，只有超出 QPS 时才会等待；回放不访问网络，无需限流
+        let limiter = ratelimit::shared(&self.config.appid, self.config.effective_qps());
+        let replaying = matches!(
+            cassette::Mode::from_config(&self.config.network),
+            cassette::Mode::Replay(_)
+        );
+
+        // 暂时性错误按配置自动重试
+        let cancel = control.cancel_token();
+        with_retry_until(&self.config.retry, cancel, |_| {
+            if !replaying {
+                limiter.acquire_until(cancel)?;
+            }
+            op()
+        })
+    }
+
+    /// 发送单个请求，`q` 必须已在字节上限之内
+    fn request(
+        &self,
+        from: &str,
+        to: &str,
+        q: &str,
+        with_dict: bool,
+        control: &JobControl,
+    ) -> Result<Translation, BtcliError> {
+        self.call(control, || {
+            let response_body =
+                send_response(&self.config.appid, from, to, q, &self.config, with_dict)?;
+
+            // 检查是否包含错误信息
+            if response_body.contains("error_msg") {
+                Err(patch_error(response_body))
+            } else {
+                // 翻译成功，返回解析后的结果
+                patch_raw(response_body)
+            }
+        })
+    }
+}
+
+impl Translator for BaiduTranslator {
+    fn name(&self) -> &'static str {
+        "baidu"
+    }
+
+    fn translate(&self, from: &str, to: &str, q: &str) -> Result<Translation, BtcliError> {
+        let mut results = self.translate_batch(from, to, &[q])?;
+        results
+            .pop()
+            .ok_or_else(|| BtcliError::Parse("empty translation result".to_string()))
+    }
+
+    fn translate_batch_with(
+        &self,
+        from: &str,
+        to: &str,
+        items: &[&str],
+        control: &JobControl,
+    ) -> Result<Vec<Translation>, BtcliError> {
+        // 未开启术语干预时，在本地用占位符保护术语表中的术语
+        let glossary = self.local_glossary()?;
+
+        // 先逐条查本地缓存，只请求未命中的条目
+        let cache = Cache::open(&self.config.cache);
+        let provider = self.cache_provider(glossary.as_ref());
+        let keys: Vec<String> = items
+            .iter()
+            .map(|q| Cache::key(&provider, from, to, q))
+            .collect();
+        let mut results: Vec<Option<Translation>> = keys
+            .iter()
+            .zip(items)
+            .map(|(key, q)| {
+                // 特殊功能：如果翻译内容为 QAS，则直接返回彩蛋
+                if q.trim().eq_ignore_ascii_case("QAS") {
+                    return Some(Translation {
+                        from: from.to_string(),
+                        to: to.to_string(),
+                        items: vec![TranslationItem::new(*q, decrypt(EGG_CODE))],
+                        cached: false,
+                    });
+                }
+                let hit = cache.get(key);
+                if hit.is_some() {
+                    log_to_file!("命中翻译缓存: {}", key);
+                }
+                hit
+            })
+            .collect();
+        let pending: Vec<usize> = (0..items.len())
+            .filter(|&index| results[index].is_none())
+            .collect();
+
+        if !pending.is_empty() {
+            let masked: Vec<Option<Masked>> = pending
+                .iter()
+                .map(|&index| {
+                    glossary
+                        .as_ref()
+                        .map(|glossary| glossary.mask(items[index]))
+                })
+                .collect();
+
+            // 所有条目按行拼成一段文本，记下每个条目占用的行号范围
+            let mut lines: Vec<&str> = Vec::new();
+            let mut ranges = Vec::with_capacity(pending.len());
+            for (slot, &index) in pending.iter().enumerate() {
+                let text = masked[slot]
+                    .as_ref()
+                    .map_or(items[index], |masked| masked.text.as_str());
+                let start = lines.len();
+                lines.extend(text.lines());
+                if lines.len() == start {
+                    lines.push("");
+                }
+                ranges.push(start..lines.len());
+            }
+
+            // 超出单次请求上限的文本按段落、句子分块，逐块发送后按原换行拼回
+            let plan = chunker::plan(&lines.join("\n"), MAX_QUERY_BYTES);
+            // 各块由工作池并发发送，结果按块顺序收集
+            let responses = pool::run_ordered(
+                plan.chunk_texts(),
+                self.config.concurrency,
+                control,
+                |chunk| self.request(from, to, &chunk, false, control),
+            )?;
+            // 每个块各自识别源语言，条目取覆盖其首个非空行的块的结果
+            let detected: Vec<String> =
+                responses.iter().map(|result| result.from.clone()).collect();
+            let translated: Vec<Vec<String>> = responses
+                .into_iter()
+                .map(|result| result.items.into_iter().map(|item| item.dst).collect())
+                .collect();
+            if plan.len() > 1 {
+                log_to_file!("{} 条文本合并为 {} 个请求", pending.len(), plan.len());
+            }
+
+            // trans_result 按行号映射回各条目，原文恢复为未替换术语的文本；
+            // 条数对不上时整批报错，不把错位的译文写入缓存
+            let assembled = plan.assemble(&translated)?;
+            for (slot, &index) in pending.iter().enumerate() {
+                let src_lines = items[index].lines().chain(std::iter::repeat(""));
+                let item_lines = ranges[slot]
+                    .clone()
+                    .zip(src_lines)
+                    .map(|(line, src)| {
+                        let dst = assembled.get(line).map_or("", |item| item.dst.as_str());
+                        let dst = match (&glossary, &masked[slot]) {
+                            (Some(glossary), Some(masked)) => glossary.unmask(masked, dst),
+                            _ => dst.to_string(),
+                        };
+                        TranslationItem::new(src, dst)
+                    })
+                    .collect();
+
+                let detected_from = ranges[slot]
+                    .clone()
+                    .find_map(|line| plan.chunk_of_line(line))
+                    .map_or(from, |chunk| detected[chunk].as_str());
+                let result = Translation {
+                    from: detected_from.to_string(),
+                    to: to.to_string(),
+                    items: item_lines,
+                    cached: false,
+                };
+                cache.put(&keys[index], &result);
+                results[index] = Some(result);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn lookup(&self, from: &str, to: &str, word: &str) -> Result<Translation, BtcliError> {
+        let word = word.trim();
+        if word.eq_ignore_ascii_case("QAS") {
+            return self.translate(from, to, word);
+        }
+        let cache = Cache::open(&self.config.cache);
+        let cache_key = Cache::key("baidu:dict", from, to, word);
+        if let Some(hit) = cache.get(&cache_key) {
+            log_to_file!("命中查词缓存: {}", cache_key);
+            return Ok(hit);
+        }
+
+        // 词典和发音只对通用翻译接口有效，查词时忽略领域设置
+        let config = AppConfig {
+            domain: None,
+            ..self.config.clone()
+        };
+        let result =
+            BaiduTranslator::new(config).request(from, to, word, true, &JobControl::default())?;
+        cache.put(&cache_key, &result);
+        Ok(result)
+    }
+
+    fn detect(&self, q: &str) -> Result<String, BtcliError> {
+        // 语种识别只需要开头的一部分文本
+        let sample = chunker::plan(q, MAX_QUERY_BYTES).chunk_texts();
+        let sample = sample
+            .first()
+            .ok_or_else(|| BtcliError::Config("No text to detect".to_string()))?;
+
+        self.call(&JobControl::default(), || {
+            let response_body = send_detect(&self.config.appid, sample, &self.config)?;
+            patch_detect(response_body)
+        })
+    }
+
+    fn supported_languages(&self) -> Vec<&'static str> {
+        crate::langs::LANGS.iter().map(|lang| lang.code).collect()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            detect: true,
+            auto_source: true,
+            max_query_bytes: MAX_QUERY_BYTES,
+            domains: true,
+            term_intervention: true,
+            dictionary: true,
+        }
+    }
+}
+
+/// 语种识别，返回百度语种代码（例如 en、zh、jp）
+pub fn detect(q: &str, app_config: AppConfig) -> Result<String, BtcliError> {
+    BaiduTranslator::new(app_config).detect(q)
+}
+
+/// 批量翻译，多条短文本合并为尽量少的请求，结果与 `items` 按下标对应
+pub fn translate_batch(
+    from: &str,
+    to: &str,
+    items: &[&str],
+    app_config: AppConfig,
+) -> Result<Vec<Translation>, BtcliError> {
+    BaiduTranslator::new(app_config).translate_batch(from, to, items)
 }
 
 /// 核心翻译函数 - 单次翻译
+///
+/// 保留旧接口，内部转交给 [`BaiduTranslator`]
 pub fn translate(
     appid: &str,
     from: &str,
     to: &str,
     q: &str,
     app_config: AppConfig,
-) -> Result<String, String> {
-    // 特殊功能：如果翻译内容为 QAS，则直接返回彩蛋
-    if q.trim().eq_ignore_ascii_case("QAS") {
-        let egg: String = decrypt(EGG_CODE);
-        return Ok(egg);
+) -> Result<String, BtcliError> {
+    let config = AppConfig {
+        appid: appid.to_string(),
+        ..app_config
+    };
+    BaiduTranslator::new(config)
+        .translate(from, to, q)
+        .map(|result| result.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_raw_keeps_all_segments() {
+        let body = r#"{"from":"en","to":"zh","trans_result":[{"src":"Hello","dst":"你好"},{"src":"World","dst":"世界"}]}"#;
+        let result = patch_raw(body.to_string()).unwrap();
+
+        assert_eq!(result.from, "en");
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.text(), "你好\n世界");
     }
 
-    // 控制请求频率
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    #[test]
+    fn test_patch_error_accepts_string_code() {
+        let body = r#"{"error_code":"54003","error_msg":"Invalid Access Limit"}"#;
+        let err = patch_error(body.to_string());
 
-    // 发送请求并获取响应
-    match send_response(appid, from, to, q, &app_config) {
-        Err(e) => {
-            return Err(format!(":( error sending request: {}", e.to_string()));
-        }
-        Ok(response_body) => {
-            // 检查是否包含错误信息
-            if response_body.contains("error_msg") {
-                // 解析错误响应
-                match patch_error(response_body) {
-                    Ok(err_resp) => {
-                        // 处理 error_code 可能是字符串或数字的情况
-                        let errcode = match err_resp.error_code.as_u64() {
-                            Some(code) => code as usize,
-                            None => {
-                                // 如果是字符串，则尝试转换
-                                match err_resp.error_code.as_str() {
-                                    Some(code_str) => match usize::from_str(code_str) {
-                                        Ok(code) => code,
-                                        Err(_) => {
-                                            return Err(":( failed to parse error code".to_string());
-                                        }
-                                    },
-                                    None => return Err(":( failed to parse error code".to_string()),
-                                }
-                            }
-                        };
+        assert_eq!(err.code(), Some(54003));
+        assert!(err.is_retryable());
+        assert!(err.is_quota());
+    }
 
-                        // 返回错误信息
-                        return Err(format!(
-                            ":( We asked, but server said: {}",
-                            query_msg(errcode)
-                        ));
-                    }
-                    Err(e) => return Err(e),
-                }
-            } else {
-                // 翻译成功，返回解析后的结果
-                return patch_raw(response_body);
-            }
-        }
+    #[test]
+    fn test_patch_raw_parses_dict_and_tts() {
+        let dict = r#"{"lang":"1","word_result":{"simple_means":{"word_name":"hello","symbols":[{"ph_en":"hə'ləʊ","ph_am":"həˈloʊ","parts":[{"part":"int.","means":["喂","哈罗"]}]}]}}}"#;
+        let body = serde_json::json!({
+            "from": "en",
+            "to": "zh",
+            "trans_result": [{
+                "src": "hello",
+                "dst": "你好",
+                "src_tts": "https://example.com/src.mp3",
+                "dst_tts": "",
+                "dict": dict,
+            }]
+        });
+        let result = patch_raw(body.to_string()).unwrap();
+        let item = &result.items[0];
+
+        assert_eq!(item.src_tts.as_deref(), Some("https://example.com/src.mp3"));
+        assert_eq!(item.dst_tts, None);
+        let entry = item.dict.as_ref().unwrap();
+        assert_eq!(entry.word, "hello");
+        assert_eq!(entry.phonetics.len(), 2);
+        assert_eq!(entry.parts[0].means, vec!["喂", "哈罗"]);
+    }
+
+    #[test]
+    fn test_field_sign_includes_domain() {
+        assert_ne!(
+            calculate_field_sign("id", "q", "1", "it", "key"),
+            calculate_sign("id", "q", "1", "key")
+        );
+        assert_eq!(
+            calculate_field_sign("id", "q", "1", "it", "key"),
+            calculate_sign("id", "q", "1it", "key")
+        );
+    }
+
+    #[test]
+    fn test_patch_detect() {
+        let ok = r#"{"error_code":0,"error_msg":"success","data":{"src":"en"}}"#;
+        assert_eq!(patch_detect(ok.to_string()).unwrap(), "en");
+
+        let err = r#"{"error_code":"54001","error_msg":"Invalid Sign"}"#;
+        assert_eq!(
+            patch_detect(err.to_string()).unwrap_err().code(),
+            Some(54001)
+        );
     }
 }
@@ -0,0 +1,288 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 术语表
+//! 从 TSV/CSV 文件加载“原文术语 -> 译文术语”，翻译前把术语替换为占位符，翻译后再换回译文术语
+
+use crate::error::BtcliError;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub source: String,
+    pub target: String,
+}
+
+/// 译文违反术语表的一处记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 行号，从 1 开始
+    pub line: usize,
+    pub source: String,
+    pub expected: String,
+}
+
+/// 翻译前替换掉的术语，用于翻译后还原
+#[derive(Debug, Clone)]
+pub struct Masked {
+    pub text: String,
+    /// 占位符序号对应的术语下标
+    slots: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    terms: Vec<Term>,
+}
+
+/// 占位符，选用翻译引擎通常原样保留的形式
+fn placeholder(index: usize) -> String {
+    format!("__BT{}__", index)
+}
+
+impl Glossary {
+    pub fn new(mut terms: Vec<Term>) -> Self {
+        // 长术语优先匹配，避免 "machine" 抢先替换 "machine learning" 的一部分
+        terms.sort_by_key(|term| std::cmp::Reverse(term.source.len()));
+        Self { terms }
+    }
+
+    /// 从文件加载，`.csv` 按逗号分隔，其余按制表符分隔
+    pub fn load(path: &Path) -> Result<Self, BtcliError> {
+        let raw = std::fs::read_to_string(path)?;
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        Self::parse(&raw, if is_csv { ',' } else { '\t' })
+    }
+
+    /// 解析术语表文本，空行和以 # 开头的行会被忽略
+    pub fn parse(raw: &str, separator: char) -> Result<Self, BtcliError> {
+        let mut terms = Vec::new();
+        for (index, line) in raw.lines().enumerate() {
+            let line = line.trim_start_matches('\u{feff}').trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = split_fields(line, separator);
+            match fields.as_slice() {
+                // 只由下划线组成的术语会匹配到占位符内部
+                [source, _, ..] if source.chars().all(|c| c == '_') => {
+                    return Err(BtcliError::Config(format!(
+                        "Invalid glossary term at line {}: {}",
+                        index + 1,
+                        line
+                    )));
+                }
+                [source, target, ..] if !target.is_empty() => {
+                    terms.push(Term {
+                        source: source.clone(),
+                        target: target.clone(),
+                    });
+                }
+                _ => {
+                    return Err(BtcliError::Config(format!(
+                        "Invalid glossary entry at line {}: {}",
+                        index + 1,
+                        line
+                    )));
+                }
+            }
+        }
+        Ok(Self::new(terms))
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// 术语表内容的指纹，术语变化后缓存随之失效
+    pub fn fingerprint(&self) -> String {
+        let raw: String = self
+            .terms
+            .iter()
+            .map(|term| format!("{}\t{}\n", term.source, term.target))
+            .collect();
+        format!("{:x}", md5::compute(raw.as_bytes()))
+    }
+
+    /// 把文本中的术语替换为占位符
+    pub fn mask(&self, text: &str) -> Masked {
+        let mut masked = Masked {
+            text: text.to_string(),
+            slots: Vec::new(),
+        };
+        // 已插入的占位符在文本中的位置，后面的术语不能匹配到占位符内部
+        let mut tokens: Vec<std::ops::Range<usize>> = Vec::new();
+        for (term_index, term) in self.terms.iter().enumerate() {
+            let mut start = 0;
+            while let Some(pos) = find_term_from(&masked.text, &term.source, start) {
+                let end = pos + term.source.len();
+                if tokens
+                    .iter()
+                    .any(|token| pos < token.end && token.start < end)
+                {
+                    start = pos + masked.text[pos..].chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+                let token = placeholder(masked.slots.len());
+                masked.text.replace_range(pos..end, &token);
+                masked.slots.push(term_index);
+
+                // 替换后其后的占位符整体平移
+                let token_end = pos + token.len();
+                for range in tokens.iter_mut().filter(|range| range.start >= end) {
+                    range.start = range.start + token_end - end;
+                    range.end = range.end + token_end - end;
+                }
+                tokens.push(pos..token_end);
+                start = token_end;
+            }
+        }
+        masked
+    }
+
+    /// 把译文中的占位符换回译文术语
+    pub fn unmask(&self, masked: &Masked, translated: &str) -> String {
+        let mut result = translated.to_string();
+        // 占位符以 __ 结尾，__BT1__ 不会匹配到 __BT10__ 的一部分
+        for (slot, term_index) in masked.slots.iter().enumerate() {
+            result = result.replace(&placeholder(slot), &self.terms[*term_index].target);
+        }
+        result
+    }
+
+    /// 逐行比对原文和译文，找出原文出现了术语但译文没有使用规定译法的位置
+    pub fn check(&self, source: &str, translated: &str) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut dst_lines = translated.lines();
+        for (index, src_line) in source.lines().enumerate() {
+            let dst_line = dst_lines.next().unwrap_or("");
+            for term in &self.terms {
+                if find_term(src_line, &term.source).is_some() && !dst_line.contains(&term.target) {
+                    violations.push(Violation {
+                        line: index + 1,
+                        source: term.source.clone(),
+                        expected: term.target.clone(),
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// 查找术语位置，纯 ASCII 术语要求前后不是字母数字，避免匹配到单词内部
+fn find_term(text: &str, term: &str) -> Option<usize> {
+    find_term_from(text, term, 0)
+}
+
+/// 从字节位置 `start` 开始查找术语
+fn find_term_from(text: &str, term: &str, mut start: usize) -> Option<usize> {
+    if term.is_empty() {
+        return None;
+    }
+    let ascii_word = term.is_ascii();
+    while let Some(offset) = text[start..].find(term) {
+        let pos = start + offset;
+        let end = pos + term.len();
+        let before_ok = !ascii_word
+            || text[..pos]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = !ascii_word
+            || text[end..]
+                .chars()
+                .next()
+                .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return Some(pos);
+        }
+        start = pos + term.chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+/// 切分一行字段，支持双引号包裹（CSV 中 "" 表示一个引号）
+fn split_fields(line: &str, separator: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                fields.push(current.trim().to_string());
+                current.clear();
+            }
+            c => current.push(c),
+        }
+    }
+    fields.push(current.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Glossary {
+        Glossary::parse(
+            "# 原文\t译文\nmachine learning\t机器学习\nRust\t\"Rust 语言\"\n",
+            '\t',
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mask_and_unmask_roundtrip() {
+        let glossary = sample();
+        let masked = glossary.mask("Rust makes machine learning fast, Rustacean.");
+        assert_eq!(masked.text, "__BT1__ makes __BT0__ fast, Rustacean.");
+
+        let restored = glossary.unmask(&masked, "__BT1__ 让 __BT0__ 更快");
+        assert_eq!(restored, "Rust 语言 让 机器学习 更快");
+    }
+
+    #[test]
+    fn test_check_reports_violations_by_line() {
+        let glossary = sample();
+        let violations = glossary.check(
+            "I like Rust\nmachine learning",
+            "我喜欢 Rust 语言\n机器的学习",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, 2);
+        assert_eq!(violations[0].expected, "机器学习");
+    }
+
+    #[test]
+    fn test_mask_skips_text_inside_placeholders() {
+        let glossary = Glossary::parse("Rust\t铁锈\nBT\t比特\n", '\t').unwrap();
+        let masked = glossary.mask("Rust and BT");
+        assert_eq!(masked.text, "__BT0__ and __BT1__");
+        assert_eq!(glossary.unmask(&masked, &masked.text), "铁锈 and 比特");
+
+        assert!(Glossary::parse("__\t下划线\n", '\t').is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_with_quotes() {
+        let glossary = Glossary::parse("\"a, b\",甲乙\n", ',').unwrap();
+        assert_eq!(glossary.terms()[0].source, "a, b");
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 翻译历史
+//! 保存在 .btcli/history.jsonl，每行一条，超过上限时丢弃最旧的记录
+
+use crate::conf::HistoryConfig;
+use crate::translator::Translation;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 一条历史记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// 翻译时间（Unix 秒）
+    pub time: u64,
+    pub from: String,
+    pub to: String,
+    pub source: String,
+    pub translation: String,
+}
+
+impl Entry {
+    pub fn new(source: &str, result: &Translation) -> Self {
+        Self {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            from: result.from.clone(),
+            to: result.to.clone(),
+            source: source.to_string(),
+            translation: result.text(),
+        }
+    }
+}
+
+pub struct History {
+    path: PathBuf,
+    config: HistoryConfig,
+}
+
+impl History {
+    /// 打开默认位置（.btcli/history.jsonl）的历史记录
+    pub fn open(config: &HistoryConfig) -> Self {
+        Self::with_path(crate::conf::config_dir().join("history.jsonl"), config)
+    }
+
+    pub fn with_path(path: PathBuf, config: &HistoryConfig) -> Self {
+        Self {
+            path,
+            config: config.clone(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一条记录，失败时只记录日志，不影响翻译结果
+    pub fn append(&self, entry: &Entry) {
+        if !self.config.enabled {
+            return;
+        }
+        if let Err(e) = self.try_append(entry) {
+            log_to_file!("写入翻译历史失败: {}", e);
+        }
+    }
+
+    fn try_append(&self, entry: &Entry) -> std::io::Result<()> {
+        let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        drop(file);
+
+        // 超出上限一定比例后才整体重写，避免每次都重写文件
+        let entries = self.load();
+        let max = self.config.max_entries;
+        if max > 0 && entries.len() > max + max / 10 {
+            let mut raw = String::new();
+            for entry in &entries[entries.len() - max..] {
+                raw.push_str(&serde_json::to_string(entry).map_err(std::io::Error::other)?);
+                raw.push('\n');
+            }
+            fs::write(&self.path, raw)?;
+        }
+        Ok(())
+    }
+
+    /// 读取全部记录，按时间从旧到新排列，无法解析的行会被跳过
+    fn load(&self) -> Vec<Entry> {
+        fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    /// 最近的 `count` 条记录，从旧到新排列
+    pub fn recent(&self, count: usize) -> Vec<Entry> {
+        let mut entries = self.load();
+        let skip = entries.len().saturating_sub(count);
+        entries.drain(..skip);
+        entries
+    }
+
+    /// 清空历史，返回删除的条目数
+    pub fn clear(&self) -> std::io::Result<usize> {
+        let count = self.load().len();
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(count),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translator::TranslationItem;
+
+    #[test]
+    fn test_append_keeps_latest_entries() {
+        let path = std::env::temp_dir().join(format!("btcli-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let config = HistoryConfig {
+            enabled: true,
+            max_entries: 10,
+        };
+        let history = History::with_path(path.clone(), &config);
+        for n in 0..30 {
+            let result = Translation {
+                from: "en".to_string(),
+                to: "zh".to_string(),
+                items: vec![TranslationItem::new(n.to_string(), format!("译{}", n))],
+                cached: false,
+            };
+            history.append(&Entry::new(&n.to_string(), &result));
+        }
+
+        let recent = history.recent(3);
+        let sources: Vec<&str> = recent.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(sources, ["27", "28", "29"]);
+        assert!(history.load().len() <= 11);
+        assert!(history.clear().unwrap() > 0);
+        assert!(history.recent(3).is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! HTTP 传输层
+//! 按 `[network]` 配置构建 ureq Agent，并在进程内复用，分块和批量请求共享同一个连接池
+
+use crate::conf::NetworkConfig;
+use crate::error::BtcliError;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+use std::time::Duration;
+use ureq::tls::{Certificate, PemItem, RootCerts, TlsConfig};
+use ureq::{Agent, Proxy};
+
+lazy_static! {
+    /// 当前使用的 Agent 及其对应的网络配置，配置变化时重建
+    static ref AGENT: Mutex<Option<(NetworkConfig, Agent)>> = Mutex::new(None);
+}
+
+/// 获取与 `config` 对应的共享 Agent
+pub fn agent(config: &NetworkConfig) -> Result<Agent, BtcliError> {
+    let mut cached = match AGENT.lock() {
+        Ok(cached) => cached,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some((built_for, agent)) = cached.as_ref()
+        && built_for == config
+    {
+        return Ok(agent.clone());
+    }
+    let agent = build_agent(config)?;
+    *cached = Some((config.clone(), agent.clone()));
+    Ok(agent)
+}
+
+fn secs(value: u64) -> Option<Duration> {
+    (value > 0).then(|| Duration::from_secs(value))
+}
+
+fn build_agent(config: &NetworkConfig) -> Result<Agent, BtcliError> {
+    let mut builder = Agent::config_builder()
+        .proxy(resolve_proxy(config)?)
+        .timeout_connect(secs(config.connect_timeout_secs))
+        .timeout_recv_response(secs(config.read_timeout_secs))
+        .timeout_recv_body(secs(config.read_timeout_secs));
+
+    if let Some(user_agent) = config.user_agent.as_deref() {
+        builder = builder.user_agent(user_agent);
+    }
+    if let Some(path) = config.ca_certs.as_deref() {
+        let tls = TlsConfig::builder()
+            .root_certs(load_ca_certs(path)?)
+            .build();
+        builder = builder.tls_config(tls);
+    }
+
+    log_to_file!(
+        "创建 HTTP Agent: proxy={:?}, connect={}s, read={}s",
+        config.proxy,
+        config.connect_timeout_secs,
+        config.read_timeout_secs
+    );
+    Ok(builder.build().into())
+}
+
+/// 配置中的代理优先；未配置时读取 HTTPS_PROXY / ALL_PROXY 等环境变量，空字符串表示不使用代理
+fn resolve_proxy(config: &NetworkConfig) -> Result<Option<Proxy>, BtcliError> {
+    match config.proxy.as_deref().map(str::trim) {
+        Some("") => Ok(None),
+        Some(url) => Proxy::new(url)
+            .map(Some)
+            .map_err(|e| BtcliError::Config(format!("Invalid proxy {}: {}", url, e))),
+        None => Ok(Proxy::try_from_env()),
+    }
+}
+
+/// 读取 PEM 格式的 CA 证书包，与内置的 Mozilla 根证书一起作为 TLS 校验的根证书
+fn load_ca_certs(path: &str) -> Result<RootCerts, BtcliError> {
+    let pem = std::fs::read(crate::conf::resolve_path(path))?;
+    let mut extra = Vec::new();
+    for item in ureq::tls::parse_pem(&pem) {
+        match item {
+            Ok(PemItem::Certificate(cert)) => extra.push(cert.to_owned()),
+            Ok(_) => {}
+            Err(e) => {
+                return Err(BtcliError::Config(format!(
+                    "Unable to parse CA bundle {}: {}",
+                    path, e
+                )));
+            }
+        }
+    }
+    if extra.is_empty() {
+        return Err(BtcliError::Config(format!(
+            "No certificate found in CA bundle {}",
+            path
+        )));
+    }
+    let mut certs: Vec<Certificate<'static>> = webpki_root_certs::TLS_SERVER_ROOT_CERTS
+        .iter()
+        .map(|cert| Certificate::from_der(cert.as_ref()))
+        .collect();
+    certs.extend(extra);
+    Ok(RootCerts::new_with_certs(&certs))
+}
+
+/// 以表单方式 POST，返回响应正文
+pub fn post_form(
+    config: &NetworkConfig,
+    url: &str,
+    params: &[(&str, &str)],
+) -> Result<String, BtcliError> {
+    let response = agent(config)?
+        .post(url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .send_form(params.iter().copied())?;
+    Ok(response.into_body().read_to_string()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_proxy_disables_env_proxy() {
+        let config = NetworkConfig {
+            proxy: Some(String::new()),
+            ..NetworkConfig::default()
+        };
+        assert!(resolve_proxy(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_configured_proxy_builds_agent() {
+        let config = NetworkConfig {
+            proxy: Some("http://127.0.0.1:3128".to_string()),
+            ..NetworkConfig::default()
+        };
+        let proxy = resolve_proxy(&config).unwrap().unwrap();
+        assert_eq!(proxy.port(), 3128);
+        assert!(agent(&config).is_ok());
+    }
+
+    #[test]
+    fn test_ca_certs_extend_builtin_roots() {
+        use base64::{Engine as _, engine::general_purpose};
+        let der = webpki_root_certs::TLS_SERVER_ROOT_CERTS[0].as_ref();
+        let pem = format!(
+            "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+            general_purpose::STANDARD.encode(der)
+        );
+        let path = std::env::temp_dir().join(format!("btcli-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, pem).unwrap();
+        let roots = load_ca_certs(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        match roots {
+            RootCerts::Specific(certs) => {
+                assert_eq!(
+                    certs.len(),
+                    webpki_root_certs::TLS_SERVER_ROOT_CERTS.len() + 1
+                )
+            }
+            _ => panic!("expected specific root certs"),
+        }
+    }
+}
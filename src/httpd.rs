@@ -0,0 +1,187 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 极简 HTTP/1.1 服务端工具
+//! 供 btcli-mock 和 `btcli serve` 共用：每个连接只处理一个请求，参数来自查询字符串和表单/JSON 请求体
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// 请求体上限，超出时返回 413
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// 请求行与请求头的总长度上限
+const MAX_HEAD_BYTES: u64 = 64 * 1024;
+/// 连接的读写超时，避免空闲连接一直占用工作线程
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 解析后的请求
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// 查询字符串与请求体中的参数，请求体中的同名参数优先
+    pub params: HashMap<String, String>,
+}
+
+/// 处理一个连接：设置读写超时，读取请求，由 `handle` 生成状态行和 JSON 响应后写回
+///
+/// 请求体超过 [`MAX_BODY_BYTES`] 时直接返回 413
+pub fn serve_connection<F>(stream: TcpStream, handle: F) -> io::Result<()>
+where
+    F: FnOnce(&Request) -> (&'static str, serde_json::Value),
+{
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let (status, payload) = match read_request(&stream) {
+        Ok(request) => handle(&request),
+        Err(e) if e.kind() == io::ErrorKind::FileTooLarge => (
+            "413 Payload Too Large",
+            serde_json::json!({"error": e.to_string()}),
+        ),
+        Err(e) => return Err(e),
+    };
+    write_response(stream, status, &payload.to_string())
+}
+
+/// 从连接中读取一个请求
+///
+/// 请求体为 JSON 对象时，其中的字符串字段也会并入 `params`；
+/// 请求体超过 [`MAX_BODY_BYTES`] 时返回 `ErrorKind::FileTooLarge`
+pub fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let limit = MAX_HEAD_BYTES + MAX_BODY_BYTES as u64;
+    let mut reader = BufReader::new(stream.try_clone()?.take(limit));
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let target = parts.next().unwrap_or("").to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::FileTooLarge,
+            format!("request body too large: {} bytes", content_length),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let mut params = parse_form(query);
+    let body = String::from_utf8_lossy(&body);
+    match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(serde_json::Value::Object(fields)) => {
+            for (name, value) in fields {
+                if let Some(value) = value.as_str() {
+                    params.insert(name, value.to_string());
+                }
+            }
+        }
+        _ => params.extend(parse_form(&body)),
+    }
+
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        params,
+    })
+}
+
+/// 写出 JSON 响应并关闭连接
+pub fn write_response(mut stream: TcpStream, status: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// 解析 application/x-www-form-urlencoded 数据
+pub fn parse_form(raw: &str) -> HashMap<String, String> {
+    raw.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_component(k), decode_component(v))
+        })
+        .collect()
+}
+
+fn decode_component(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_form() {
+        let form = parse_form("q=Hello+world%21%0A%E4%BD%A0%E5%A5%BD&from=en");
+        assert_eq!(form["q"], "Hello world!\n你好");
+        assert_eq!(form["from"], "en");
+    }
+
+    #[test]
+    fn test_oversized_body_is_rejected() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        let (stream, _) = listener.accept().unwrap();
+        serve_connection(stream, |_| {
+            unreachable!("oversized request reached handler")
+        })
+        .unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 413"));
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 语种注册表
+//! 百度使用自己的语种代码（jp、kor、fra……），这里统一收录并支持 ISO 639 / BCP-47 别名
+
+use crate::error::BtcliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lang {
+    /// 百度语种代码
+    pub code: &'static str,
+    pub name_zh: &'static str,
+    pub name_en: &'static str,
+    /// ISO 639-1/639-2 与常见 BCP-47 写法，均为小写
+    pub aliases: &'static [&'static str],
+}
+
+impl Lang {
+    /// 是否可以作为目标语言（auto 只能用作源语言）
+    pub fn is_target(&self) -> bool {
+        self.code != "auto"
+    }
+}
+
+#[rustfmt::skip]
+pub const LANGS: [Lang; 29] = [
+    Lang { code: "auto", name_zh: "自动检测", name_en: "Auto Detect", aliases: &["detect"] },
+    Lang { code: "zh", name_zh: "中文", name_en: "Chinese", aliases: &["zh-cn", "zh-hans", "zh-sg", "zho", "chi", "cmn", "chs"] },
+    Lang { code: "en", name_zh: "英语", name_en: "English", aliases: &["eng", "en-us", "en-gb"] },
+    Lang { code: "yue", name_zh: "粤语", name_en: "Cantonese", aliases: &["zh-yue"] },
+    Lang { code: "wyw", name_zh: "文言文", name_en: "Classical Chinese", aliases: &["lzh", "zh-classical"] },
+    Lang { code: "jp", name_zh: "日语", name_en: "Japanese", aliases: &["ja", "jpn"] },
+    Lang { code: "kor", name_zh: "韩语", name_en: "Korean", aliases: &["ko"] },
+    Lang { code: "fra", name_zh: "法语", name_en: "French", aliases: &["fr", "fre"] },
+    Lang { code: "spa", name_zh: "西班牙语", name_en: "Spanish", aliases: &["es"] },
+    Lang { code: "th", name_zh: "泰语", name_en: "Thai", aliases: &["tha"] },
+    Lang { code: "ara", name_zh: "阿拉伯语", name_en: "Arabic", aliases: &["ar"] },
+    Lang { code: "ru", name_zh: "俄语", name_en: "Russian", aliases: &["rus"] },
+    Lang { code: "pt", name_zh: "葡萄牙语", name_en: "Portuguese", aliases: &["por"] },
+    Lang { code: "de", name_zh: "德语", name_en: "German", aliases: &["deu", "ger"] },
+    Lang { code: "it", name_zh: "意大利语", name_en: "Italian", aliases: &["ita"] },
+    Lang { code: "el", name_zh: "希腊语", name_en: "Greek", aliases: &["ell", "gre"] },
+    Lang { code: "nl", name_zh: "荷兰语", name_en: "Dutch", aliases: &["nld", "dut"] },
+    Lang { code: "pl", name_zh: "波兰语", name_en: "Polish", aliases: &["pol"] },
+    Lang { code: "bul", name_zh: "保加利亚语", name_en: "Bulgarian", aliases: &["bg"] },
+    Lang { code: "est", name_zh: "爱沙尼亚语", name_en: "Estonian", aliases: &["et"] },
+    Lang { code: "dan", name_zh: "丹麦语", name_en: "Danish", aliases: &["da"] },
+    Lang { code: "fin", name_zh: "芬兰语", name_en: "Finnish", aliases: &["fi"] },
+    Lang { code: "cs", name_zh: "捷克语", name_en: "Czech", aliases: &["ces", "cze"] },
+    Lang { code: "rom", name_zh: "罗马尼亚语", name_en: "Romanian", aliases: &["ro", "ron", "rum"] },
+    // slo 是百度的斯洛文尼亚语代码；ISO 639-2 中 slo 指斯洛伐克语，因此不把 slk/sk 之类映射到这里
+    Lang { code: "slo", name_zh: "斯洛文尼亚语", name_en: "Slovenian", aliases: &["sl", "slv"] },
+    Lang { code: "swe", name_zh: "瑞典语", name_en: "Swedish", aliases: &["sv"] },
+    Lang { code: "hu", name_zh: "匈牙利语", name_en: "Hungarian", aliases: &["hun"] },
+    Lang { code: "cht", name_zh: "繁体中文", name_en: "Traditional Chinese", aliases: &["zh-tw", "zh-hk", "zh-mo", "zh-hant"] },
+    Lang { code: "vie", name_zh: "越南语", name_en: "Vietnamese", aliases: &["vi"] },
+];
+
+/// 语种表之外、百度高级版账户可用的其他语种代码，校验时原样放行
+#[rustfmt::skip]
+pub const EXTENDED_CODES: &[&str] = &[
+    "afr", "alb", "amh", "arm", "aze", "baq", "bel", "ben", "bos", "bur",
+    "cat", "epo", "fil", "geo", "gle", "glg", "guj", "hau", "heb", "hi",
+    "hkm", "hrv", "ice", "id", "kan", "kaz", "kir", "lao", "lat", "lav",
+    "lit", "mac", "mal", "mar", "may", "mlt", "mon", "nep", "nor", "pan",
+    "per", "sin", "srp", "swa", "tam", "tel", "tgk", "tr", "ukr", "urd",
+    "uzb", "wel", "xho", "yor", "zul",
+];
+
+/// 按百度代码、别名或 BCP-47 标签查找语种，不区分大小写
+///
+/// 带地区的标签（例如 en-AU、ja-JP）先整体匹配，再退回到主语言子标签
+pub fn lookup(input: &str) -> Option<&'static Lang> {
+    let tag = input.trim().to_ascii_lowercase().replace('_', "-");
+    if tag.is_empty() {
+        return None;
+    }
+    let find = |tag: &str| {
+        LANGS
+            .iter()
+            .find(|lang| lang.code == tag || lang.aliases.contains(&tag))
+    };
+    find(&tag).or_else(|| {
+        // zh-Hant-TW 之类的三段标签先试前两段，再试主语言
+        let mut subtags: Vec<&str> = tag.split('-').collect();
+        while subtags.len() > 1 {
+            subtags.pop();
+            if let Some(lang) = find(&subtags.join("-")) {
+                return Some(lang);
+            }
+        }
+        None
+    })
+}
+
+/// 校验并规范化源语言，返回百度代码
+///
+/// 语种表之外、属于 [`EXTENDED_CODES`] 的代码原样传给接口
+pub fn normalize_source(input: &str) -> Result<String, BtcliError> {
+    match lookup(input) {
+        Some(lang) => Ok(lang.code.to_string()),
+        None => pass_through(input),
+    }
+}
+
+/// 校验并规范化目标语言，返回百度代码
+pub fn normalize_target(input: &str) -> Result<String, BtcliError> {
+    match lookup(input) {
+        Some(lang) if lang.is_target() => Ok(lang.code.to_string()),
+        Some(_) => Err(BtcliError::Config(
+            "auto can only be used as the source language".to_string(),
+        )),
+        None => pass_through(input),
+    }
+}
+
+/// 语种表之外的扩展代码不做转换，其余输入视为拼写错误
+fn pass_through(input: &str) -> Result<String, BtcliError> {
+    let code = input.trim().to_ascii_lowercase();
+    if EXTENDED_CODES.contains(&code.as_str()) {
+        log_to_file!("语种 {} 不在内置列表中，按原样传给翻译接口", code);
+        Ok(code)
+    } else {
+        Err(unsupported(input))
+    }
+}
+
+/// 不支持的语种，附带拼写最接近的代码或别名
+fn unsupported(input: &str) -> BtcliError {
+    let candidates = LANGS
+        .iter()
+        .flat_map(|lang| std::iter::once(lang.code).chain(lang.aliases.iter().copied()))
+        .chain(EXTENDED_CODES.iter().copied());
+    let hint = match crate::cli::suggest(&input.trim().to_ascii_lowercase(), candidates) {
+        Some(suggestion) => format!(", did you mean {}?", suggestion),
+        None => String::new(),
+    };
+    BtcliError::Config(format!(
+        "Unsupported language: {}{} (run `btcli langs` to see the list)",
+        input, hint
+    ))
+}
+
+/// 终端显示宽度，中日韩字符按两列计算
+pub(crate) fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+pub(crate) fn pad(s: &str, width: usize) -> String {
+    let fill = width.saturating_sub(display_width(s));
+    format!("{}{}", s, " ".repeat(fill))
+}
+
+/// 生成帮助信息中的常见语种表，每行三组“名称 代码”
+pub fn help_table() -> String {
+    let mut table = String::new();
+    for _ in 0..3 {
+        table.push_str(&format!("{}{}", pad("名称", 14), pad("代码", 7)));
+    }
+    table = table.trim_end().to_string();
+    table.push('\n');
+    for row in LANGS.chunks(3) {
+        let line: String = row
+            .iter()
+            .map(|lang| format!("{}{}", pad(lang.name_zh, 14), pad(lang.code, 7)))
+            .collect();
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// `btcli langs` 的完整列表，包含英文名和别名
+pub fn list_table() -> String {
+    let mut table = format!(
+        "{}{}{}{}\n",
+        pad("代码", 7),
+        pad("名称", 14),
+        pad("English", 22),
+        "别名"
+    );
+    for lang in LANGS.iter() {
+        table.push_str(&format!(
+            "{}{}{}{}\n",
+            pad(lang.code, 7),
+            pad(lang.name_zh, 14),
+            pad(lang.name_en, 22),
+            lang.aliases.join(", ")
+        ));
+    }
+    table.push_str(&format!(
+        "\n高级版账户还可使用: {}\n",
+        EXTENDED_CODES.join(" ")
+    ));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_map_to_baidu_codes() {
+        assert_eq!(normalize_target("ja").unwrap(), "jp");
+        assert_eq!(normalize_target("KO").unwrap(), "kor");
+        assert_eq!(normalize_target("fr").unwrap(), "fra");
+        assert_eq!(normalize_target("zh-TW").unwrap(), "cht");
+        assert_eq!(normalize_target("zh_Hant_TW").unwrap(), "cht");
+        assert_eq!(normalize_target("en-AU").unwrap(), "en");
+    }
+
+    #[test]
+    fn test_auto_only_valid_as_source() {
+        assert_eq!(normalize_source("auto").unwrap(), "auto");
+        assert!(normalize_target("auto").is_err());
+    }
+
+    #[test]
+    fn test_unknown_language_is_rejected() {
+        assert!(normalize_source("klingon").is_err());
+        assert!(normalize_target("x1").is_err());
+    }
+
+    #[test]
+    fn test_unlisted_codes_pass_through() {
+        assert_eq!(normalize_target("hi").unwrap(), "hi");
+        assert_eq!(normalize_source("Ukr").unwrap(), "ukr");
+        assert!(normalize_target("xx").is_err());
+        let error = normalize_target("jq").unwrap_err().to_string();
+        assert!(error.contains("did you mean jp?"), "{}", error);
+    }
+}
@@ -93,13 +93,35 @@ impl FileLogger {
 }
 
 // 导出必要的模块
+pub mod cache;
+pub mod cassette;
+pub mod chunker;
 pub mod cli;
+pub mod completions;
 pub mod conf;
+pub mod doctor;
+pub mod encoding;
+pub mod error;
 pub mod expect_react;
 pub mod extract_help;
 pub mod fancy_egg;
 pub mod fycore;
+pub mod glossary;
+pub mod history;
 pub mod fyerrcodes;
+pub mod http;
+pub mod httpd;
+pub mod langs;
+pub mod manpage;
+pub mod mock;
+pub mod output;
+pub mod pool;
+pub mod ratelimit;
+pub mod repl;
+pub mod retry;
+pub mod server;
+pub mod stream;
+pub mod translator;
 
 // 仅在启用UI特性时包含UI模块
 #[cfg(feature = "ui")]
@@ -5,10 +5,12 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use std::env;
+use std::io::IsTerminal;
 
 // 引入lib.rs中的模块和宏
 use btcli_lib::*;
-mod cli;
+use btcli_lib::error::exit;
+use btcli_lib::output::{OutputFormat, Report};
 
 fn main() {
     // 初始化日志系统 - 根据配置决定是否记录日志
@@ -21,9 +23,9 @@ fn main() {
 
     let args: Vec<String> = env::args().collect();
 
-    // 如果提供了命令行参数，则使用纯命令行模式
-    if args.len() > 1 {
-        run_cli_mode(&args[1..]);
+    // 如果提供了命令行参数，或标准输入来自管道/重定向，则使用纯命令行模式
+    let code = if args.len() > 1 || !std::io::stdin().is_terminal() {
+        run_cli_mode(&args[1..])
     } else {
         // 否则运行UI模式（如果启用了UI特性）
         #[cfg(feature = "ui")]
@@ -31,36 +33,84 @@ fn main() {
             log_to_file!("启动UI模式");
             crate::ui::loader::ui_main();
             log_to_file!("UI模式结束");
+            exit::OK
         }
+        // 未启用UI特性时进入行模式交互
         #[cfg(not(feature = "ui"))]
         {
-            eprintln!("UI功能未启用。请使用命令行参数运行此程序。");
-            cli::show_help();
+            log_to_file!("UI功能未启用，启动行模式交互");
+            run_cli_mode(&["repl".to_string()])
         }
-    }
-    log_to_file!("应用程序结束");
+    };
+    log_to_file!("应用程序结束，退出码 {}", code);
+    std::process::exit(code);
 }
 
-fn run_cli_mode(args: &[String]) {
-    let cli_args = cli::parse_args(args);
+/// 运行命令行模式，返回进程退出码
+fn run_cli_mode(args: &[String]) -> i32 {
+    let cli_args = match cli::parse_args(args) {
+        Ok(cli_args) => cli_args,
+        Err(e) => {
+            log_to_file!("命令行参数错误: {}", e);
+            eprintln!("错误: {}", e);
+            eprintln!("运行 btcli --help 查看用法");
+            return e.exit_code();
+        }
+    };
     
     if cli_args.help {
         cli::show_help();
-        return;
+        return exit::OK;
     }
     
     if cli_args.version {
         cli::show_version();
-        return;
+        return exit::OK;
     }
     
-    if cli_args.text.is_empty() {
+    // 管道或 - 参数时从标准输入读取原文
+    let mut cli_args = cli_args;
+    // 来自文件或标准输入的整段文本不写入翻译历史
+    let text_from_input = cli::wants_stdin(&cli_args) || cli_args.file.is_some();
+    if cli::wants_stdin(&cli_args) {
+        match cli::read_stdin() {
+            Ok(text) => cli_args.text = text,
+            Err(e) => {
+                eprintln!("错误: 读取标准输入失败: {}", e);
+                return exit::FAILURE;
+            }
+        }
+    }
+
+    // 指定了文件时以文件内容作为原文
+    if let Some(path) = &cli_args.file {
+        match crate::encoding::read_file(path) {
+            Ok((text, encoding)) => {
+                log_to_file!("读取文件 {}，编码 {}", path, encoding);
+                cli_args.text = text;
+            }
+            Err(e) => {
+                eprintln!("错误: 读取文件 {} 失败: {}", path, e);
+                return e.exit_code();
+            }
+        }
+    }
+
+    let needs_text = cli_args.standalone.is_none()
+        && matches!(cli_args.command, None | Some(cli::Command::Detect))
+        && !cli_args.stream;
+    if needs_text && cli_args.text.trim().is_empty() && cli_args.batch.is_none() {
         eprintln!("错误: 请提供要翻译的文本");
         cli::show_help();
-        return;
+        return exit::USAGE;
     }
     
     log_to_file!("启动CLI模式，参数: {:?}", cli_args);
+
+    // 以下命令不需要（或需要自行检查）配置文件
+    if let Some(command) = cli_args.standalone {
+        return run_standalone(command);
+    }
     
     // 尝试加载配置
     let config = match crate::conf::try_init_conf() {
@@ -74,31 +124,384 @@ fn run_cli_mode(args: &[String]) {
         }
         Err(error_msg) => {
             log_to_file!("配置加载失败: {}", error_msg);
-            eprintln!("配置错误: {}", error_msg);
-            return;
+            return cli::report_error("配置错误", &error_msg, cli_args.format);
         }
     };
     
+    // 命令行缓存开关
+    let mut config = config;
+    if cli_args.no_cache {
+        config.cache.enabled = false;
+    }
+    config.cache.refresh = cli_args.refresh;
+    if cli_args.domain.is_some() {
+        config.domain = cli_args.domain.clone();
+    }
+    if cli_args.record.is_some() {
+        config.network.record = cli_args.record.clone();
+        config.network.replay = None;
+    }
+    if cli_args.replay.is_some() {
+        config.network.replay = cli_args.replay.clone();
+        config.network.record = None;
+    }
+
+    if let Some(command) = &cli_args.command {
+        let code = run_command(command, &cli_args, &config);
+        log_to_file!("CLI模式结束");
+        return code;
+    }
+
     // 使用命令行参数覆盖配置中的语言设置
-    let source_lang = cli_args.source_lang.unwrap_or(config.source_lang.clone());
-    let target_lang = cli_args.target_lang.unwrap_or(config.target_lang.clone());
-
-    // 执行翻译
-    match crate::fycore::translate(
-        &config.appid,
-        &source_lang,
-        &target_lang,
-        &cli_args.text,
-        config.clone(),
-    ) {
+    let source_lang = cli_args.source_lang.clone().unwrap_or(config.source_lang.clone());
+    let target_lang = cli_args.target_lang.clone().unwrap_or(config.target_lang.clone());
+
+    // 按配置选择翻译引擎
+    let translator = match crate::translator::from_config(&config) {
+        Ok(translator) => translator,
+        Err(error_msg) => {
+            log_to_file!("翻译引擎创建失败: {}", error_msg);
+            return cli::report_error("配置错误", &error_msg, cli_args.format);
+        }
+    };
+
+    // 长文本和批量任务在标准错误上显示进度条
+    let control = crate::pool::JobControl::new().with_progress(cli::render_progress);
+
+    if cli_args.stream {
+        let options = crate::stream::StreamOptions::new(&source_lang, &target_lang);
+        let code = match crate::stream::stream_stdin(translator.as_ref(), &options) {
+            Ok(None) => exit::OK,
+            // 出错的行已原样输出，退出码反映第一个翻译错误
+            Ok(Some(e)) => e.exit_code(),
+            Err(e) => {
+                log_to_file!("流式翻译中断: {}", e);
+                eprintln!("错误: {}", e);
+                exit::FAILURE
+            }
+        };
+        log_to_file!("CLI模式结束");
+        return code;
+    }
+
+    if let Some(input) = &cli_args.batch {
+        let code =
+            run_batch(input, translator.as_ref(), (&source_lang, &target_lang), &control, &cli_args);
+        log_to_file!("CLI模式结束");
+        return code;
+    }
+
+    // 执行翻译，查词模式额外请求词典和发音
+    let started = std::time::Instant::now();
+    let outcome = if cli_args.dict {
+        translator.lookup(&source_lang, &target_lang, &cli_args.text)
+    } else {
+        translator
+            .translate_batch_with(&source_lang, &target_lang, &[&cli_args.text], &control)
+            .and_then(|mut results| {
+                results.pop().ok_or_else(|| {
+                    crate::error::BtcliError::Parse("empty translation result".to_string())
+                })
+            })
+    };
+    let code = match outcome {
         Ok(result) => {
-            log_to_file!("翻译成功完成");
-            println!("{}", result);
+            log_to_file!("翻译成功完成，共 {} 段", result.items.len());
+            if !text_from_input {
+                crate::history::History::open(&config.history)
+                    .append(&crate::history::Entry::new(&cli_args.text, &result));
+            }
+            let rendered = if cli_args.dict && cli_args.format == OutputFormat::Text {
+                cli::format_dict_card(&result)
+            } else {
+                let report = Report::new(
+                    &cli_args.text,
+                    (&source_lang, &target_lang),
+                    translator.name(),
+                    &result,
+                    started.elapsed(),
+                );
+                crate::output::render(cli_args.format, &[report])
+            };
+            // 自动检测时在标准错误输出识别到的源语言，不影响管道中的译文
+            if source_lang == "auto" && cli_args.format == OutputFormat::Text {
+                eprintln!("[{} -> {}]", result.from, result.to);
+            }
+            match cli::write_output(&rendered, &cli_args) {
+                Ok(()) => exit::OK,
+                Err(e) => {
+                    log_to_file!("写出译文失败: {}", e);
+                    eprintln!("错误: 写出译文失败: {}", e);
+                    exit::FAILURE
+                }
+            }
         }
         Err(error_msg) => {
             log_to_file!("翻译失败: {}", error_msg);
-            eprintln!("翻译错误: {}", error_msg);
+            cli::report_error("翻译错误", &error_msg, cli_args.format)
         }
-    }
+    };
     log_to_file!("CLI模式结束");
+    code
+}
+
+/// 批量模式：每行一条，译文按原顺序逐行输出
+fn run_batch(
+    input: &str,
+    translator: &dyn crate::translator::Translator,
+    langs: (&str, &str),
+    control: &crate::pool::JobControl,
+    cli_args: &cli::CliArgs,
+) -> i32 {
+    let raw = if input == "-" {
+        let mut raw = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut raw).map(|_| raw)
+    } else {
+        std::fs::read_to_string(input)
+    };
+    let raw = match raw {
+        Ok(raw) => raw,
+        Err(e) => {
+            eprintln!("读取批量输入失败: {}", e);
+            return exit::FAILURE;
+        }
+    };
+
+    let items: Vec<&str> = raw.lines().collect();
+    log_to_file!("批量翻译 {} 条", items.len());
+    let started = std::time::Instant::now();
+    match translator.translate_batch_with(langs.0, langs.1, &items, control) {
+        Ok(results) => {
+            let latency = started.elapsed();
+            let reports: Vec<Report> = items
+                .iter()
+                .zip(&results)
+                .map(|(item, result)| Report::new(item, langs, translator.name(), result, latency))
+                .collect();
+            let rendered = crate::output::render(cli_args.format, &reports);
+            match cli::write_output(&rendered, cli_args) {
+                Ok(()) => exit::OK,
+                Err(e) => {
+                    eprintln!("错误: 写出译文失败: {}", e);
+                    exit::FAILURE
+                }
+            }
+        }
+        Err(error_msg) => {
+            log_to_file!("批量翻译失败: {}", error_msg);
+            cli::report_error("翻译错误", &error_msg, cli_args.format)
+        }
+    }
+}
+
+/// 执行不需要配置文件的子命令
+fn run_standalone(command: cli::StandaloneCommand) -> i32 {
+    match command {
+        cli::StandaloneCommand::Langs => {
+            print!("{}", crate::langs::list_table());
+            exit::OK
+        }
+        cli::StandaloneCommand::ConfigPath => {
+            println!("{}", crate::conf::get_config_path().display());
+            exit::OK
+        }
+        cli::StandaloneCommand::Doctor => run_doctor(),
+        cli::StandaloneCommand::Completions(shell) => {
+            print!("{}", crate::completions::generate(shell));
+            exit::OK
+        }
+        cli::StandaloneCommand::Manpage => {
+            print!("{}", crate::manpage::render());
+            exit::OK
+        }
+    }
+}
+
+fn run_command(
+    command: &cli::Command,
+    cli_args: &cli::CliArgs,
+    config: &crate::conf::AppConfig,
+) -> i32 {
+    match command {
+        cli::Command::Detect => {
+            let detected = crate::translator::from_config(config)
+                .and_then(|translator| translator.detect(&cli_args.text));
+            match detected {
+                Ok(lang) if cli_args.format.is_json() => {
+                    println!("{}", serde_json::json!({"source": cli_args.text, "lang": lang}));
+                    exit::OK
+                }
+                Ok(lang) => {
+                    println!("{}", lang);
+                    exit::OK
+                }
+                Err(error_msg) => {
+                    log_to_file!("语种识别失败: {}", error_msg);
+                    cli::report_error("语种识别错误", &error_msg, cli_args.format)
+                }
+            }
+        }
+        cli::Command::GlossaryCheck { source, translated } => {
+            run_glossary_check(source, translated, config)
+        }
+        cli::Command::Exec(argv) => run_exec(argv, cli_args, config),
+        cli::Command::Repl => {
+            let source_lang = cli_args.source_lang.clone().unwrap_or(config.source_lang.clone());
+            let target_lang = cli_args.target_lang.clone().unwrap_or(config.target_lang.clone());
+            match crate::repl::run(config, &source_lang, &target_lang) {
+                Ok(()) => exit::OK,
+                Err(e) => cli::report_error("交互模式错误", &e, cli_args.format),
+            }
+        }
+        cli::Command::Config => {
+            // 不在终端上显示密钥
+            let mut shown = config.clone();
+            shown.key = "******".to_string();
+            match toml::to_string(&shown) {
+                Ok(raw) => {
+                    println!("# {}", crate::conf::get_config_path().display());
+                    print!("{}", raw);
+                    exit::OK
+                }
+                Err(e) => {
+                    eprintln!("配置错误: {}", e);
+                    exit::CONFIG
+                }
+            }
+        }
+        cli::Command::History(action) => {
+            let history = crate::history::History::open(&config.history);
+            match action {
+                cli::HistoryAction::List(count) => {
+                    for entry in history.recent(*count) {
+                        let time = chrono::DateTime::from_timestamp(entry.time as i64, 0)
+                            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"))
+                            .map(|t| t.to_string())
+                            .unwrap_or_default();
+                        println!(
+                            "{} [{} -> {}] {} => {}",
+                            time,
+                            entry.from,
+                            entry.to,
+                            entry.source.replace('\n', " "),
+                            entry.translation.replace('\n', " ")
+                        );
+                    }
+                    exit::OK
+                }
+                cli::HistoryAction::Clear => match history.clear() {
+                    Ok(removed) => {
+                        println!("已清除 {} 条翻译历史", removed);
+                        exit::OK
+                    }
+                    Err(e) => {
+                        eprintln!("清除翻译历史失败: {}", e);
+                        exit::FAILURE
+                    }
+                },
+            }
+        }
+        cli::Command::Serve => {
+            let addr = cli_args.listen.as_deref().unwrap_or(crate::server::DEFAULT_LISTEN);
+            match crate::server::serve(addr, config) {
+                Ok(()) => exit::OK,
+                Err(e) => cli::report_error("服务启动失败", &e, cli_args.format),
+            }
+        }
+        cli::Command::Cache(action) => {
+            let cache = crate::cache::Cache::open(&config.cache);
+            match action {
+                cli::CacheAction::Stats => {
+                    let stats = cache.stats();
+                    println!("缓存目录: {}", cache.dir().display());
+                    println!("条目数: {}", stats.entries);
+                    println!("已过期: {}", stats.expired);
+                    println!("占用空间: {} 字节", stats.bytes);
+                    exit::OK
+                }
+                cli::CacheAction::Clear => match cache.clear() {
+                    Ok(removed) => {
+                        println!("已清除 {} 条缓存", removed);
+                        exit::OK
+                    }
+                    Err(e) => {
+                        eprintln!("清除缓存失败: {}", e);
+                        exit::FAILURE
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// doctor 子命令：逐项输出检查结果，返回第一个失败项对应的退出码
+fn run_doctor() -> i32 {
+    let checks = crate::doctor::run();
+    for check in &checks {
+        println!("{}", check);
+    }
+    crate::doctor::exit_code(&checks)
+}
+
+/// exec 子命令：运行命令并逐行翻译其输出，返回命令自身的退出码
+fn run_exec(argv: &[String], cli_args: &cli::CliArgs, config: &crate::conf::AppConfig) -> i32 {
+    let translator = match crate::translator::from_config(config) {
+        Ok(translator) => translator,
+        Err(error_msg) => return cli::report_error("配置错误", &error_msg, cli_args.format),
+    };
+    let source_lang = cli_args.source_lang.clone().unwrap_or(config.source_lang.clone());
+    let target_lang = cli_args.target_lang.clone().unwrap_or(config.target_lang.clone());
+    let options = crate::stream::StreamOptions::new(&source_lang, &target_lang);
+    match crate::stream::exec(argv, translator.as_ref(), &options) {
+        Ok(code) => {
+            log_to_file!("命令 {:?} 退出码 {}", argv, code);
+            code
+        }
+        Err(e) => {
+            log_to_file!("运行命令 {:?} 失败: {}", argv, e);
+            eprintln!("错误: 无法运行 {}: {}", argv[0], e);
+            127
+        }
+    }
+}
+
+/// 术语检查，发现不符合术语表的译文时返回失败，便于在 CI 中使用
+fn run_glossary_check(source: &str, translated: &str, config: &crate::conf::AppConfig) -> i32 {
+    let path = match config.glossary.path.as_deref() {
+        Some(path) if !path.is_empty() => crate::conf::resolve_path(path),
+        _ => {
+            eprintln!("配置错误: 未在配置文件的 [glossary] 中设置 path");
+            return exit::CONFIG;
+        }
+    };
+    let glossary = match crate::glossary::Glossary::load(&path) {
+        Ok(glossary) => glossary,
+        Err(e) => {
+            eprintln!("术语表加载失败: {}", e);
+            return exit::CONFIG;
+        }
+    };
+    let (source_text, translated_text) =
+        match (std::fs::read_to_string(source), std::fs::read_to_string(translated)) {
+            (Ok(s), Ok(t)) => (s, t),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("读取文件失败: {}", e);
+                return exit::FAILURE;
+            }
+        };
+
+    let violations = glossary.check(&source_text, &translated_text);
+    for violation in &violations {
+        println!(
+            "第 {} 行: 术语 \"{}\" 应译为 \"{}\"",
+            violation.line, violation.source, violation.expected
+        );
+    }
+    if violations.is_empty() {
+        println!("译文符合术语表（共 {} 条术语）", glossary.terms().len());
+        exit::OK
+    } else {
+        println!("共发现 {} 处不符合术语表", violations.len());
+        exit::FAILURE
+    }
 }
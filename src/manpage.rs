@@ -0,0 +1,95 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! man 手册页生成（`btcli manpage`）
+//! 内容取自 [`crate::cli`] 中与帮助信息相同的定义，输出 roff 格式，可保存为 btcli.1
+
+use crate::cli::{COMMANDS, EXAMPLES, EXIT_CODES, OPTIONS, USAGE, option_synopsis};
+use crate::langs::LANGS;
+
+/// 转义 roff 特殊字符：反斜杠、连字符，以及行首的 `.` 和 `'`
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', r"\e").replace('-', r"\-");
+    if escaped.starts_with(['.', '\'']) {
+        format!(r"\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// 一个带粗体标签的段落
+fn tagged(page: &mut String, tag: &str, text: &str) {
+    page.push_str(&format!(".TP\n\\fB{}\\fR\n{}\n", escape(tag), escape(text)));
+}
+
+/// 生成完整的手册页
+pub fn render() -> String {
+    let mut page = format!(
+        ".TH BTCLI 1 \"\" \"btcli {}\" \"User Commands\"\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    page.push_str(".SH 名称\nbtcli \\- 命令行翻译工具\n");
+
+    page.push_str(".SH 用法\n");
+    for (line, help) in USAGE {
+        tagged(&mut page, line, help);
+    }
+
+    page.push_str(&format!(
+        ".SH 描述\n{}\n.PP\n配置文件为可执行文件所在目录下的 .btcli/config.toml，首次运行时自动创建。\n",
+        escape(env!("CARGO_PKG_DESCRIPTION"))
+    ));
+
+    page.push_str(".SH 选项\n");
+    for spec in OPTIONS {
+        tagged(&mut page, &option_synopsis(spec), spec.help);
+    }
+
+    page.push_str(".SH 命令\n");
+    for spec in COMMANDS {
+        let usage = format!("{} {}", spec.name, spec.args);
+        tagged(&mut page, usage.trim_end(), spec.help);
+    }
+
+    page.push_str(".SH 语种\n\\-s/\\-t 使用百度语种代码，也接受列出的别名。\n");
+    for lang in LANGS.iter() {
+        let mut text = format!("{} ({})", lang.name_zh, lang.name_en);
+        if !lang.aliases.is_empty() {
+            text.push_str(&format!("，别名: {}", lang.aliases.join(", ")));
+        }
+        tagged(&mut page, lang.code, &text);
+    }
+
+    page.push_str(".SH 退出码\n");
+    for (code, help) in EXIT_CODES {
+        tagged(&mut page, &code.to_string(), help);
+    }
+
+    page.push_str(".SH 示例\n");
+    for (line, help) in EXAMPLES {
+        tagged(&mut page, line, help);
+    }
+
+    page.push_str(".SH 文件\n");
+    tagged(&mut page, ".btcli/config.toml", "配置文件");
+    tagged(&mut page, ".btcli/history.jsonl", "翻译历史");
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_options() {
+        let page = render();
+        assert!(page.starts_with(".TH BTCLI 1"));
+        assert!(page.contains(r"\fB\-s, \-\-source LANG\fR"));
+        assert!(page.contains(r"\fB\&.btcli/config.toml\fR"));
+        // 不应出现未转义的行首控制字符
+        assert!(page.lines().all(|line| !line.starts_with('\'')));
+    }
+}
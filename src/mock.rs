@@ -0,0 +1,275 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 离线的百度翻译模拟服务
+//! 按百度协议校验 appid 和 sign，返回 trans_result / 语种识别结果，也可以注入 fyerrcodes 中的任意错误码
+//! 配合配置中的 `api_base` 使用，便于在没有网络和凭据的机器上演示和做集成测试
+
+use crate::fycore::{
+    DETECT_PATH, FIELD_PATH, TRANSLATE_PATH, calculate_field_sign, calculate_sign,
+};
+use crate::fyerrcodes;
+use crate::httpd;
+use crate::langs;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 模拟服务的设置
+#[derive(Debug, Clone)]
+pub struct MockOptions {
+    pub appid: String,
+    pub key: String,
+    /// 每个请求都返回该错误码
+    pub always_error: Option<usize>,
+}
+
+impl Default for MockOptions {
+    fn default() -> Self {
+        Self {
+            appid: "mock-appid".to_string(),
+            key: "mock-key".to_string(),
+            always_error: None,
+        }
+    }
+}
+
+struct State {
+    options: MockOptions,
+    /// 依次返回给后续请求的错误码，用完后恢复正常
+    queue: Mutex<VecDeque<usize>>,
+    requests: AtomicUsize,
+}
+
+/// 在后台线程运行的模拟服务，随进程退出
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+}
+
+impl MockServer {
+    /// 在 `addr` 上启动服务，端口为 0 时由系统分配
+    pub fn start(addr: &str, options: MockOptions) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(State {
+            options,
+            queue: Mutex::new(VecDeque::new()),
+            requests: AtomicUsize::new(0),
+        });
+        let worker = Arc::clone(&state);
+        std::thread::spawn(move || accept_loop(listener, worker));
+        Ok(Self { addr, state })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// 作为 `api_base` 使用的地址
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// 让接下来的一个请求返回 `code`，多次调用按顺序生效
+    pub fn inject(&self, code: usize) {
+        lock(&self.state.queue).push_back(code);
+    }
+
+    /// 已处理的请求数
+    pub fn request_count(&self) -> usize {
+        self.state.requests.load(Ordering::SeqCst)
+    }
+}
+
+/// 在前台运行服务，直到进程退出
+pub fn serve(addr: &str, options: MockOptions, inject: &[usize]) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let state = Arc::new(State {
+        options,
+        queue: Mutex::new(inject.iter().copied().collect()),
+        requests: AtomicUsize::new(0),
+    });
+    accept_loop(listener, state);
+    Ok(())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn accept_loop(listener: TcpListener, state: Arc<State>) {
+    for stream in listener.incoming().flatten() {
+        let state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &state) {
+                log_to_file!("模拟服务处理请求失败: {}", e);
+            }
+        });
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &State) -> io::Result<()> {
+    httpd::serve_connection(stream, |request| {
+        state.requests.fetch_add(1, Ordering::SeqCst);
+        if request.method == "POST" || request.method == "GET" {
+            route(&request.path, &request.params, state)
+        } else {
+            (
+                "405 Method Not Allowed",
+                json!({"error": "method not allowed"}),
+            )
+        }
+    })
+}
+
+fn route(
+    path: &str,
+    params: &HashMap<String, String>,
+    state: &State,
+) -> (&'static str, serde_json::Value) {
+    let endpoint = match path {
+        TRANSLATE_PATH | FIELD_PATH | DETECT_PATH => path,
+        _ => return ("404 Not Found", json!({"error": "not found"})),
+    };
+    let result = check_request(endpoint, params, state).and_then(|_| {
+        if endpoint == DETECT_PATH {
+            Ok(detect_response(&params["q"]))
+        } else {
+            translate_response(params)
+        }
+    });
+    match result {
+        Ok(payload) => ("200 OK", payload),
+        Err(code) => ("200 OK", error_response(code)),
+    }
+}
+
+/// 依次检查注入的错误、必填参数、appid 和签名
+fn check_request(
+    endpoint: &str,
+    params: &HashMap<String, String>,
+    state: &State,
+) -> Result<(), usize> {
+    if let Some(code) = lock(&state.queue).pop_front() {
+        return Err(code);
+    }
+    if let Some(code) = state.options.always_error {
+        return Err(code);
+    }
+
+    let mut required = vec!["appid", "q", "salt", "sign"];
+    if endpoint != DETECT_PATH {
+        required.extend(["from", "to"]);
+    }
+    if endpoint == FIELD_PATH {
+        required.push("domain");
+    }
+    if required
+        .iter()
+        .any(|name| params.get(*name).is_none_or(|v| v.is_empty()))
+    {
+        return Err(54000);
+    }
+
+    let options = &state.options;
+    if params["appid"] != options.appid {
+        return Err(52003);
+    }
+    let expected = if endpoint == FIELD_PATH {
+        calculate_field_sign(
+            &params["appid"],
+            &params["q"],
+            &params["salt"],
+            &params["domain"],
+            &options.key,
+        )
+    } else {
+        calculate_sign(
+            &params["appid"],
+            &params["q"],
+            &params["salt"],
+            &options.key,
+        )
+    };
+    if params["sign"] != expected {
+        return Err(54001);
+    }
+    Ok(())
+}
+
+fn translate_response(params: &HashMap<String, String>) -> Result<serde_json::Value, usize> {
+    let q = &params["q"];
+    let from = match langs::lookup(&params["from"]) {
+        Some(lang) if lang.code == "auto" => detect_lang(q),
+        Some(lang) => lang.code,
+        None => return Err(58001),
+    };
+    let to = match langs::lookup(&params["to"]) {
+        Some(lang) if lang.is_target() => lang.code,
+        _ => return Err(58001),
+    };
+    let with_tts = params.get("tts").is_some_and(|v| v == "1");
+
+    let items: Vec<serde_json::Value> = q
+        .lines()
+        .map(|line| {
+            let mut item = json!({"src": line, "dst": mock_translate(line, to)});
+            if with_tts {
+                item["src_tts"] = json!(format!("https://mock.invalid/tts/{}.mp3", from));
+                item["dst_tts"] = json!(format!("https://mock.invalid/tts/{}.mp3", to));
+            }
+            item
+        })
+        .collect();
+    Ok(json!({"from": from, "to": to, "trans_result": items}))
+}
+
+/// 模拟译文：给原文加上目标语言标记，便于在输出中辨认
+fn mock_translate(line: &str, to: &str) -> String {
+    format!("[{}] {}", to, line)
+}
+
+fn detect_response(q: &str) -> serde_json::Value {
+    json!({"error_code": 0, "error_msg": "success", "data": {"src": detect_lang(q)}})
+}
+
+/// 按字符范围粗略识别语种
+fn detect_lang(q: &str) -> &'static str {
+    for c in q.chars() {
+        match c as u32 {
+            0x3040..=0x30FF => return "jp",
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => return "kor",
+            0x4E00..=0x9FFF => return "zh",
+            0x0400..=0x04FF => return "ru",
+            _ => {}
+        }
+    }
+    "en"
+}
+
+fn error_response(code: usize) -> serde_json::Value {
+    // 百度返回的 error_code 是字符串
+    json!({"error_code": code.to_string(), "error_msg": fyerrcodes::query_msg(code)})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_lang_by_script() {
+        assert_eq!(detect_lang("こんにちは"), "jp");
+        assert_eq!(detect_lang("你好"), "zh");
+        assert_eq!(detect_lang("Hello"), "en");
+    }
+}
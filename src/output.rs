@@ -0,0 +1,184 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 命令行输出格式
+//! text 为给人看的译文；json、jsonl、tsv 供脚本使用，错误也以带错误码的 JSON 对象输出
+
+use crate::error::BtcliError;
+use crate::translator::{Translation, TranslationItem};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// `--format` 可选的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// 单个 JSON 对象，批量时为数组
+    Json,
+    /// 每条结果一行紧凑 JSON
+    Jsonl,
+    /// 每个片段一行：原文<TAB>译文
+    Tsv,
+}
+
+impl OutputFormat {
+    /// `--format` 接受的名称，用于补全
+    pub const NAMES: &[&str] = &["text", "json", "jsonl", "tsv"];
+
+    pub fn parse(name: &str) -> Result<Self, BtcliError> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" | "ndjson" => Ok(Self::Jsonl),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(BtcliError::Config(format!(
+                "Unsupported output format: {} (expected text, json, jsonl or tsv)",
+                name
+            ))),
+        }
+    }
+
+    /// 错误是否以 JSON 输出
+    pub fn is_json(self) -> bool {
+        matches!(self, Self::Json | Self::Jsonl)
+    }
+}
+
+/// 一条翻译结果的机器可读描述
+#[derive(Debug, Clone, Serialize)]
+pub struct Report<'a> {
+    /// 原文
+    pub source: &'a str,
+    /// 译文（各片段按换行拼接）
+    pub translation: String,
+    /// 请求时指定的源语言，可能为 auto
+    pub requested_from: &'a str,
+    pub requested_to: &'a str,
+    /// 实际（识别出）的源语言
+    pub from: &'a str,
+    pub to: &'a str,
+    pub provider: &'a str,
+    /// 结果是否来自本地缓存
+    pub cached: bool,
+    /// 整次调用的耗时（毫秒），批量时各条相同
+    pub latency_ms: u64,
+    pub segments: &'a [TranslationItem],
+}
+
+impl<'a> Report<'a> {
+    pub fn new(
+        source: &'a str,
+        requested: (&'a str, &'a str),
+        provider: &'a str,
+        result: &'a Translation,
+        latency: Duration,
+    ) -> Self {
+        Self {
+            source,
+            translation: result.text(),
+            requested_from: requested.0,
+            requested_to: requested.1,
+            from: &result.from,
+            to: &result.to,
+            provider,
+            cached: result.cached,
+            latency_ms: latency.as_millis() as u64,
+            segments: &result.items,
+        }
+    }
+}
+
+/// 按格式渲染一组结果，返回带结尾换行的文本
+pub fn render(format: OutputFormat, reports: &[Report]) -> String {
+    let mut out = String::new();
+    match format {
+        OutputFormat::Text => {
+            for report in reports {
+                out.push_str(&report.translation);
+                out.push('\n');
+            }
+        }
+        OutputFormat::Json => {
+            let json = match reports {
+                [single] => serde_json::to_string_pretty(single),
+                _ => serde_json::to_string_pretty(reports),
+            };
+            out.push_str(&json.unwrap_or_default());
+            out.push('\n');
+        }
+        OutputFormat::Jsonl => {
+            for report in reports {
+                out.push_str(&serde_json::to_string(report).unwrap_or_default());
+                out.push('\n');
+            }
+        }
+        OutputFormat::Tsv => {
+            for segment in reports.iter().flat_map(|report| report.segments) {
+                out.push_str(&format!(
+                    "{}\t{}\n",
+                    escape_tsv(&segment.src),
+                    escape_tsv(&segment.dst)
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// TSV 字段中的反斜杠、制表符和换行写成转义形式
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// 错误的 JSON 表示：接口错误带 `fyerrcodes` 中的错误码和描述
+pub fn error_json(error: &BtcliError) -> serde_json::Value {
+    let message = match error {
+        BtcliError::Api { message, .. } => message.clone(),
+        other => other.to_string().trim_start_matches(":( ").to_string(),
+    };
+    json!({
+        "error": {
+            "kind": error.kind(),
+            "code": error.code(),
+            "message": message,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_carries_code() {
+        let value = error_json(&BtcliError::api(54001, ""));
+        assert_eq!(value["error"]["kind"], "api");
+        assert_eq!(value["error"]["code"], 54001);
+        assert_eq!(
+            value["error"]["message"],
+            "签名错误，请检查签名生成方法是否有误"
+        );
+        assert!(error_json(&BtcliError::Cancelled)["error"]["code"].is_null());
+    }
+
+    #[test]
+    fn test_tsv_escapes_tabs() {
+        let result = Translation {
+            from: "en".to_string(),
+            to: "zh".to_string(),
+            items: vec![TranslationItem::new("a\tb", "甲")],
+            cached: true,
+        };
+        let report = Report::new("a\tb", ("auto", "zh"), "baidu", &result, Duration::ZERO);
+        assert_eq!(render(OutputFormat::Tsv, &[report]), "a\\tb\t甲\n");
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 并发翻译工作池
+//! 多个工作线程同时发送请求，结果按提交顺序收集；实际请求频率仍受账户共享的令牌桶限制
+
+use crate::error::BtcliError;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 取消标记，可在其他线程（例如 TUI 的按钮回调）中调用 `cancel`
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// 等待 `duration`，期间被取消时立即返回 `BtcliError::Cancelled`
+    pub fn sleep(&self, duration: Duration) -> Result<(), BtcliError> {
+        const SLICE: Duration = Duration::from_millis(50);
+        let deadline = Instant::now() + duration;
+        loop {
+            if self.is_cancelled() {
+                return Err(BtcliError::Cancelled);
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Ok(());
+            }
+            std::thread::sleep(left.min(SLICE));
+        }
+    }
+}
+
+/// 任务进度：已完成的请求数和总请求数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+pub type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// 一次翻译任务的控制项：取消标记和进度回调
+#[derive(Clone, Default)]
+pub struct JobControl {
+    cancel: CancelToken,
+    progress: Option<ProgressFn>,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    pub fn report(&self, progress: Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// 以最多 `concurrency` 个线程执行 `work`，返回的结果与 `jobs` 顺序一致
+///
+/// 任一任务失败时其余线程不再领取新任务，返回下标最小的错误；被取消时返回 `BtcliError::Cancelled`
+pub fn run_ordered<T, R, F>(
+    jobs: Vec<T>,
+    concurrency: usize,
+    control: &JobControl,
+    work: F,
+) -> Result<Vec<R>, BtcliError>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Result<R, BtcliError> + Sync,
+{
+    let total = jobs.len();
+    let workers = concurrency.max(1).min(total);
+    let queue: Mutex<VecDeque<(usize, T)>> = Mutex::new(jobs.into_iter().enumerate().collect());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+    let failure: Mutex<Option<(usize, BtcliError)>> = Mutex::new(None);
+    let stop = AtomicBool::new(false);
+    let done = AtomicUsize::new(0);
+
+    control.report(Progress { done: 0, total });
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    if stop.load(Ordering::SeqCst) || control.is_cancelled() {
+                        break;
+                    }
+                    let Some((index, job)) = lock(&queue).pop_front() else {
+                        break;
+                    };
+                    match work(job) {
+                        Ok(result) => {
+                            // 持锁计数并回报，保证进度按 done 递增的顺序送达
+                            let mut results = lock(&results);
+                            results[index] = Some(result);
+                            let done = done.fetch_add(1, Ordering::SeqCst) + 1;
+                            control.report(Progress { done, total });
+                        }
+                        Err(e) => {
+                            stop.store(true, Ordering::SeqCst);
+                            let mut failure = lock(&failure);
+                            if failure.as_ref().is_none_or(|(first, _)| index < *first) {
+                                *failure = Some((index, e));
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    if let Some((_, e)) = failure.into_inner().unwrap_or_else(|p| p.into_inner()) {
+        return Err(e);
+    }
+    let results = results.into_inner().unwrap_or_else(|p| p.into_inner());
+    if results.iter().any(Option::is_none) {
+        return Err(BtcliError::Cancelled);
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_keep_submission_order() {
+        let jobs: Vec<u64> = (0..20).collect();
+        let results = run_ordered(jobs, 4, &JobControl::new(), |n| {
+            std::thread::sleep(Duration::from_millis(20 - n));
+            Ok(n * 2)
+        })
+        .unwrap();
+        assert_eq!(results, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_progress_reaches_total() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&reports);
+        let control = JobControl::new().with_progress(move |p: Progress| lock(&seen).push(p.done));
+        run_ordered((0..50).collect(), 8, &control, Ok::<u64, BtcliError>).unwrap();
+        assert_eq!(*lock(&reports), (0..=50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cancelled_job_stops_early() {
+        let cancel = CancelToken::new();
+        let control = JobControl::new().with_cancel(cancel.clone());
+        let result = run_ordered((0..10).collect(), 1, &control, |n: i32| {
+            if n == 2 {
+                cancel.cancel();
+            }
+            Ok(n)
+        });
+        assert!(matches!(result, Err(BtcliError::Cancelled)));
+    }
+
+    #[test]
+    fn test_sleep_returns_when_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let started = Instant::now();
+        assert!(matches!(
+            cancel.sleep(Duration::from_secs(5)),
+            Err(BtcliError::Cancelled)
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 令牌桶限流器
+//! 同一个 appid 在进程内共享一个令牌桶，多线程和批量请求都受同一 QPS 约束
+
+use crate::error::BtcliError;
+use crate::pool::CancelToken;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+struct Bucket {
+    /// 每秒补充的令牌数
+    rate: f64,
+    /// 桶容量，即允许的突发请求数
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// 创建每秒 `qps` 个请求的限流器，桶初始为满，首个请求无需等待
+    pub fn new(qps: f64) -> Self {
+        let rate = qps.max(0.01);
+        let capacity = rate.floor().max(1.0);
+        Self {
+            bucket: Mutex::new(Bucket {
+                rate,
+                capacity,
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// 调整速率，保留桶中已有的令牌
+    pub fn set_qps(&self, qps: f64) {
+        if let Ok(mut bucket) = self.bucket.lock() {
+            bucket.refill(Instant::now());
+            bucket.rate = qps.max(0.01);
+            bucket.capacity = bucket.rate.floor().max(1.0);
+            bucket.tokens = bucket.tokens.min(bucket.capacity);
+        }
+    }
+
+    /// 尝试立即取得一个令牌，取不到时返回需要等待的时间
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = match self.bucket.lock() {
+            Ok(bucket) => bucket,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        bucket.refill(Instant::now());
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / bucket.rate))
+        }
+    }
+
+    /// 取得一个令牌，必要时阻塞等待
+    pub fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// 同 [`RateLimiter::acquire`]，等待期间被取消时返回 `BtcliError::Cancelled`
+    pub fn acquire_until(&self, cancel: &CancelToken) -> Result<(), BtcliError> {
+        while let Err(wait) = self.try_acquire() {
+            cancel.sleep(wait)?;
+        }
+        Ok(())
+    }
+}
+
+lazy_static! {
+    static ref LIMITERS: Mutex<HashMap<String, Arc<RateLimiter>>> = Mutex::new(HashMap::new());
+}
+
+/// 获取某个账户共享的限流器，不存在时按 `qps` 创建
+pub fn shared(account: &str, qps: f64) -> Arc<RateLimiter> {
+    let mut limiters = match LIMITERS.lock() {
+        Ok(limiters) => limiters,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let limiter = limiters
+        .entry(account.to_string())
+        .or_insert_with(|| Arc::new(RateLimiter::new(qps)));
+    limiter.set_qps(qps);
+    Arc::clone(limiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_request_does_not_wait() {
+        let limiter = RateLimiter::new(1.0);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_second_request_waits_at_one_qps() {
+        let limiter = RateLimiter::new(1.0);
+        limiter.acquire();
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_shared_limiter_is_per_account() {
+        let a = shared("test-account-a", 10.0);
+        let b = shared("test-account-a", 10.0);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 行模式交互翻译（`btcli repl`）
+//! 不依赖全屏界面，适合慢速 SSH 和编辑器内置终端；上下键可以调出本次输入过的内容，开启 [history] 后还包括以往翻译过的原文
+
+use crate::conf::AppConfig;
+use crate::error::BtcliError;
+use crate::history::{Entry, History};
+use crate::translator::{Translation, Translator};
+use base64::{Engine as _, engine::general_purpose};
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+
+/// 启动时从翻译历史中载入的原文条数
+const RECALL: usize = 200;
+
+const HELP: &str = "\
+直接输入文本即可翻译，可用的命令：
+  :to <语种>      切换目标语言
+  :from <语种>    切换源语言（auto 为自动检测）
+  :swap           交换源语言和目标语言
+  :copy           复制上一条译文到剪贴板
+  :dict <单词>    查词，显示音标、释义和发音链接
+  :help           显示此帮助
+  :quit           退出（也可以按 Ctrl-D）
+以冒号开头的原文请写成两个冒号，例如 ::)";
+
+/// 一行输入对应的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Translate(String),
+    To(String),
+    From(String),
+    Swap,
+    Copy,
+    Dict(String),
+    Help,
+    Quit,
+}
+
+/// 解析一行输入，空行返回 `None`
+pub fn parse_line(line: &str) -> Result<Option<Action>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let Some(command) = line.strip_prefix(':') else {
+        return Ok(Some(Action::Translate(line.to_string())));
+    };
+    if command.starts_with(':') {
+        return Ok(Some(Action::Translate(command.to_string())));
+    }
+
+    let (name, arg) = match command.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (command, ""),
+    };
+    let required = |usage: &str| match arg.is_empty() {
+        true => Err(format!("用法: {}", usage)),
+        false => Ok(arg.to_string()),
+    };
+    let action = match name {
+        "to" => Action::To(required(":to <语种>")?),
+        "from" => Action::From(required(":from <语种>")?),
+        "swap" => Action::Swap,
+        "copy" => Action::Copy,
+        "dict" => Action::Dict(required(":dict <单词>")?),
+        "help" | "h" | "?" => Action::Help,
+        "quit" | "q" | "exit" => Action::Quit,
+        _ => return Err(format!("未知命令 :{}，输入 :help 查看可用命令", name)),
+    };
+    Ok(Some(action))
+}
+
+/// 错误说明，附带处理建议
+fn describe(error: &BtcliError) -> String {
+    match error.hint() {
+        Some(hint) => format!("{}\n提示: {}", error, hint),
+        None => error.to_string(),
+    }
+}
+
+/// 通过 OSC 52 转义序列让终端写入剪贴板
+///
+/// 远程 SSH 会话中也能复制到本地剪贴板，不依赖 X11 等图形环境
+fn copy_to_terminal(text: &str) -> std::io::Result<()> {
+    use std::io::{IsTerminal, Write};
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return Err(std::io::Error::other("标准输出不是终端"));
+    }
+    let encoded = general_purpose::STANDARD.encode(text);
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+/// 交互会话的状态
+pub struct Session {
+    translator: Box<dyn Translator>,
+    history: History,
+    pub from: String,
+    pub to: String,
+    /// 最近一次识别出的源语言，源语言为 auto 时 `:swap` 使用
+    detected: Option<String>,
+    /// 上一条译文，供 `:copy` 使用
+    last: Option<String>,
+}
+
+impl Session {
+    pub fn new(config: &AppConfig, from: &str, to: &str) -> Result<Self, BtcliError> {
+        Ok(Self {
+            translator: crate::translator::from_config(config)?,
+            history: History::open(&config.history),
+            from: from.to_string(),
+            to: to.to_string(),
+            detected: None,
+            last: None,
+        })
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("[{} -> {}] > ", self.from, self.to)
+    }
+
+    /// 执行一个操作，返回要显示的内容
+    pub fn execute(&mut self, action: Action) -> Result<String, String> {
+        match action {
+            Action::Translate(text) => {
+                let result = self
+                    .translator
+                    .translate(&self.from, &self.to, &text)
+                    .map_err(|e| describe(&e))?;
+                Ok(self.finish(&text, &result, result.text()))
+            }
+            Action::Dict(word) => {
+                let result = self
+                    .translator
+                    .lookup(&self.from, &self.to, &word)
+                    .map_err(|e| describe(&e))?;
+                let card = crate::cli::format_dict_card(&result);
+                Ok(self.finish(&word, &result, card.trim_end().to_string()))
+            }
+            Action::To(lang) => {
+                self.to = crate::langs::normalize_target(&lang).map_err(|e| describe(&e))?;
+                Ok(format!("目标语言: {}", self.to))
+            }
+            Action::From(lang) => {
+                self.from = crate::langs::normalize_source(&lang).map_err(|e| describe(&e))?;
+                Ok(format!("源语言: {}", self.from))
+            }
+            Action::Swap => {
+                let from = match self.from.as_str() {
+                    "auto" => self
+                        .detected
+                        .clone()
+                        .ok_or("源语言为 auto，翻译一次后才能确定要交换的语种")?,
+                    from => from.to_string(),
+                };
+                self.from = std::mem::replace(&mut self.to, from);
+                Ok(format!("{} -> {}", self.from, self.to))
+            }
+            Action::Copy => {
+                let last = self.last.as_deref().ok_or("还没有可复制的译文")?;
+                copy_to_terminal(last).map_err(|e| format!("无法复制到剪贴板: {}", e))?;
+                Ok("译文已复制到剪贴板".to_string())
+            }
+            Action::Help => Ok(HELP.to_string()),
+            Action::Quit => Ok(String::new()),
+        }
+    }
+
+    /// 记录译文和历史，自动检测时在译文前标出识别到的语种
+    fn finish(&mut self, source: &str, result: &Translation, rendered: String) -> String {
+        self.history.append(&Entry::new(source, result));
+        self.detected = Some(result.from.clone());
+        self.last = Some(result.text());
+        match self.from.as_str() {
+            "auto" => format!("[{} -> {}]\n{}", result.from, result.to, rendered),
+            _ => rendered,
+        }
+    }
+}
+
+/// 运行交互循环，直到 `:quit` 或 Ctrl-D
+pub fn run(config: &AppConfig, from: &str, to: &str) -> Result<(), BtcliError> {
+    let mut session = Session::new(config, from, to)?;
+    let mut editor = DefaultEditor::new().map_err(std::io::Error::other)?;
+    for entry in session.history.recent(RECALL) {
+        if !entry.source.contains('\n') {
+            let _ = editor.add_history_entry(entry.source);
+        }
+    }
+
+    println!("btcli repl，输入 :help 查看命令，Ctrl-D 退出");
+    loop {
+        let line = match editor.readline(&session.prompt()) {
+            Ok(line) => line,
+            // Ctrl-C 只放弃当前行
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(std::io::Error::other(e).into()),
+        };
+        let _ = editor.add_history_entry(line.as_str());
+        match parse_line(&line) {
+            Ok(None) => {}
+            Ok(Some(Action::Quit)) => break,
+            Ok(Some(action)) => match session.execute(action) {
+                Ok(output) => println!("{}", output),
+                Err(message) => eprintln!("错误: {}", message),
+            },
+            Err(message) => eprintln!("{}", message),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("  "), Ok(None));
+        assert_eq!(
+            parse_line("hello world"),
+            Ok(Some(Action::Translate("hello world".to_string())))
+        );
+        assert_eq!(parse_line(":to ja"), Ok(Some(Action::To("ja".to_string()))));
+        assert_eq!(parse_line(":swap"), Ok(Some(Action::Swap)));
+        assert_eq!(
+            parse_line("::)"),
+            Ok(Some(Action::Translate(":)".to_string())))
+        );
+        assert!(parse_line(":to").is_err());
+        assert!(parse_line(":tos ja").is_err());
+    }
+}
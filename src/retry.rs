@@ -0,0 +1,121 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 暂时性错误的自动重试（指数退避 + 随机抖动）
+
+use crate::conf::RetryPolicy;
+use crate::error::BtcliError;
+use crate::pool::CancelToken;
+use rand::Rng;
+use std::time::Duration;
+
+impl RetryPolicy {
+    /// 第 `attempt` 次失败后（从 1 开始）应等待的时间
+    pub fn delay_for(&self, attempt: u32, error: &BtcliError) -> Duration {
+        // 百度明确要求 54005 在 3 秒后再试，不参与指数退避
+        if error.code() == Some(54005) {
+            return Duration::from_millis(self.long_query_delay_ms);
+        }
+
+        let exp = self
+            .base_delay_ms
+            .saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+        let capped = exp.min(self.max_delay_ms);
+        // 抖动取等待时间的 0~50%，避免多个进程同时醒来
+        let jitter = rand::rng().random_range(0..=capped / 2);
+        Duration::from_millis(capped.saturating_add(jitter).min(self.max_delay_ms))
+    }
+}
+
+/// 按策略执行 `op`，仅在错误可重试时再次尝试
+///
+/// `op` 的参数为当前尝试次数（从 1 开始）
+pub fn with_retry<T, F>(policy: &RetryPolicy, op: F) -> Result<T, BtcliError>
+where
+    F: FnMut(u32) -> Result<T, BtcliError>,
+{
+    with_retry_until(policy, &CancelToken::new(), op)
+}
+
+/// 同 [`with_retry`]，退避等待期间被取消时返回 `BtcliError::Cancelled`
+pub fn with_retry_until<T, F>(
+    policy: &RetryPolicy,
+    cancel: &CancelToken,
+    mut op: F,
+) -> Result<T, BtcliError>
+where
+    F: FnMut(u32) -> Result<T, BtcliError>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_retryable() && attempt < max_attempts => {
+                let delay = policy.delay_for(attempt, &e);
+                log_to_file!(
+                    "第 {}/{} 次请求失败: {}，{} 毫秒后重试",
+                    attempt,
+                    max_attempts,
+                    e,
+                    delay.as_millis()
+                );
+                cancel.sleep(delay)?;
+                attempt += 1;
+            }
+            Err(e) => {
+                if attempt > 1 {
+                    log_to_file!("共尝试 {} 次后放弃: {}", attempt, e);
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 1,
+            max_delay_ms: 2,
+            long_query_delay_ms: 1,
+        }
+    }
+
+    #[test]
+    fn test_retries_transient_errors_until_success() {
+        let result = with_retry(&fast_policy(), |attempt| {
+            if attempt < 3 {
+                Err(BtcliError::api(52002, ""))
+            } else {
+                Ok(attempt)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_does_not_retry_auth_errors() {
+        let mut calls = 0;
+        let result: Result<(), _> = with_retry(&fast_policy(), |_| {
+            calls += 1;
+            Err(BtcliError::api(54001, ""))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_long_query_uses_fixed_delay() {
+        let policy = RetryPolicy::default();
+        let delay = policy.delay_for(1, &BtcliError::api(54005, ""));
+        assert_eq!(delay, Duration::from_millis(3000));
+    }
+}
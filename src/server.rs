@@ -0,0 +1,139 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 本地翻译服务（`btcli serve`）
+//! 在本机提供 JSON 接口，编辑器插件和脚本无需各自处理签名、缓存和限流：
+//! - `GET /health`
+//! - `GET|POST /translate`，参数 q、from、to（查询字符串、表单或 JSON 请求体）
+//! - `GET|POST /detect`，参数 q
+//! - `GET /langs`
+
+use crate::conf::AppConfig;
+use crate::error::BtcliError;
+use crate::httpd::{self, Request};
+use crate::output::{Report, error_json};
+use crate::translator::Translator;
+use serde_json::json;
+use std::net::{TcpListener, TcpStream};
+use std::time::Instant;
+
+/// 默认监听地址，只接受本机连接
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8788";
+
+/// 同时处理的连接数上限，多出的连接在监听队列中等待
+const WORKERS: usize = 4;
+
+struct Service {
+    translator: Box<dyn Translator>,
+    source_lang: String,
+    target_lang: String,
+}
+
+/// 在 `addr` 上运行服务，直到进程退出
+///
+/// 固定数量的工作线程轮流接受连接，避免本机客户端无限制地占用线程和翻译额度
+pub fn serve(addr: &str, config: &AppConfig) -> Result<(), BtcliError> {
+    let service = Service {
+        translator: crate::translator::from_config(config)?,
+        source_lang: config.source_lang.clone(),
+        target_lang: config.target_lang.clone(),
+    };
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("btcli serve 正在监听 http://{}", listener.local_addr()?);
+    log_to_file!("本地翻译服务启动: {}", addr);
+
+    std::thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| {
+                for stream in listener.incoming().flatten() {
+                    if let Err(e) = handle_connection(stream, &service) {
+                        log_to_file!("本地翻译服务处理请求失败: {}", e);
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, service: &Service) -> std::io::Result<()> {
+    httpd::serve_connection(stream, |request| match route(request, service) {
+        Ok(payload) => ("200 OK", payload),
+        Err(e) => (status_of(&e), error_json(&e)),
+    })
+}
+
+fn route(request: &Request, service: &Service) -> Result<serde_json::Value, BtcliError> {
+    if request.method != "GET" && request.method != "POST" {
+        return Err(BtcliError::Http { status: 405 });
+    }
+    match request.path.as_str() {
+        "/health" => Ok(json!({"status": "ok", "provider": service.translator.name()})),
+        "/translate" => translate(request, service),
+        "/detect" => {
+            let q = required_text(request)?;
+            let lang = service.translator.detect(q)?;
+            Ok(json!({"source": q, "lang": lang}))
+        }
+        "/langs" => Ok(json!(
+            crate::langs::LANGS
+                .iter()
+                .map(
+                    |lang| json!({"code": lang.code, "name": lang.name_zh, "name_en": lang.name_en})
+                )
+                .collect::<Vec<_>>()
+        )),
+        _ => Err(BtcliError::Http { status: 404 }),
+    }
+}
+
+fn translate(request: &Request, service: &Service) -> Result<serde_json::Value, BtcliError> {
+    let q = required_text(request)?;
+    let from = match request.params.get("from") {
+        Some(from) => crate::langs::normalize_source(from)?,
+        None => service.source_lang.clone(),
+    };
+    let to = match request.params.get("to") {
+        Some(to) => crate::langs::normalize_target(to)?,
+        None => service.target_lang.clone(),
+    };
+    let (from, to) = (from.as_str(), to.as_str());
+
+    let started = Instant::now();
+    let result = service
+        .translator
+        .translate_batch(from, to, &[q])?
+        .pop()
+        .ok_or_else(|| BtcliError::Parse("empty translation result".to_string()))?;
+    let report = Report::new(
+        q,
+        (from, to),
+        service.translator.name(),
+        &result,
+        started.elapsed(),
+    );
+    serde_json::to_value(report).map_err(|e| BtcliError::Parse(e.to_string()))
+}
+
+fn required_text(request: &Request) -> Result<&str, BtcliError> {
+    request
+        .params
+        .get("q")
+        .map(String::as_str)
+        .filter(|q| !q.trim().is_empty())
+        .ok_or_else(|| BtcliError::BadRequest("Missing parameter: q".to_string()))
+}
+
+/// 错误对应的 HTTP 状态码：请求本身的问题为 4xx，上游翻译服务的问题为 502
+fn status_of(error: &BtcliError) -> &'static str {
+    match error {
+        BtcliError::Http { status: 404 } => "404 Not Found",
+        BtcliError::Http { status: 405 } => "405 Method Not Allowed",
+        BtcliError::BadRequest(_) | BtcliError::Config(_) => "400 Bad Request",
+        BtcliError::Api { code: 58001, .. } => "400 Bad Request",
+        _ => "502 Bad Gateway",
+    }
+}
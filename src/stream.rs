@@ -0,0 +1,342 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 逐行流式翻译
+//! 用于 `--stream` 管道过滤和 `btcli exec -- <命令>`：相邻到达的行合并为一个请求，
+//! 保留 ANSI 颜色控制码，已经是目标语言（本地判断）或没有文字的行原样输出
+
+use crate::error::BtcliError;
+use crate::translator::Translator;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// 流式翻译设置
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    pub from: String,
+    pub to: String,
+    /// 收到一行后最多再等待多久，把紧随其后的行合并到同一请求
+    pub flush: Duration,
+    /// 单个请求最多合并的行数
+    pub max_lines: usize,
+}
+
+impl StreamOptions {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            flush: Duration::from_millis(300),
+            max_lines: 100,
+        }
+    }
+}
+
+/// 拆开后的一行：行首控制码、可见文本、行尾控制码
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AnsiLine {
+    prefix: String,
+    text: String,
+    suffix: String,
+    /// 文本中间也有控制码（翻译后无法对应位置，只能去掉）
+    styled_inside: bool,
+}
+
+impl AnsiLine {
+    fn parse(line: &str) -> Self {
+        let mut segments: Vec<(bool, &str)> = Vec::new();
+        let mut rest = line;
+        while !rest.is_empty() {
+            let len = escape_len(rest);
+            if len > 0 {
+                segments.push((true, &rest[..len]));
+                rest = &rest[len..];
+            } else {
+                let end = rest.find('\x1b').filter(|&i| i > 0).unwrap_or(rest.len());
+                segments.push((false, &rest[..end]));
+                rest = &rest[end..];
+            }
+        }
+
+        let first_text = segments.iter().position(|(code, _)| !code);
+        let last_text = segments.iter().rposition(|(code, _)| !code);
+        let (Some(first), Some(last)) = (first_text, last_text) else {
+            return Self {
+                prefix: line.to_string(),
+                text: String::new(),
+                suffix: String::new(),
+                styled_inside: false,
+            };
+        };
+        let join = |range: &[(bool, &str)], codes: bool| -> String {
+            range
+                .iter()
+                .filter(|(code, _)| *code == codes)
+                .map(|(_, s)| *s)
+                .collect()
+        };
+        Self {
+            prefix: join(&segments[..first], true),
+            text: join(&segments[first..=last], false),
+            suffix: join(&segments[last + 1..], true),
+            styled_inside: segments[first..=last].iter().any(|(code, _)| *code),
+        }
+    }
+
+    /// 用译文替换可见文本，保留行首行尾的控制码
+    fn render(&self, text: &str) -> String {
+        let mut line = format!("{}{}{}", self.prefix, text, self.suffix);
+        if self.styled_inside && self.suffix.is_empty() {
+            line.push_str("\x1b[0m");
+        }
+        line
+    }
+}
+
+/// 行首控制序列的字节长度，不是控制序列时返回 0
+fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&0x1b) || bytes.len() < 2 {
+        return 0;
+    }
+    match bytes[1] {
+        // CSI：ESC [ 参数 终止字节(0x40-0x7E)
+        b'[' => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7e).contains(b))
+            .map_or(bytes.len(), |i| i + 3),
+        // OSC：ESC ] ... BEL 或 ESC \
+        b']' => {
+            let body = &bytes[2..];
+            match body.iter().position(|&b| b == 0x07 || b == 0x1b) {
+                Some(i) if body[i] == 0x07 => i + 3,
+                Some(i) => (i + 4).min(bytes.len()),
+                None => bytes.len(),
+            }
+        }
+        // 其他两字节序列
+        b if b.is_ascii() => 2,
+        _ => 1,
+    }
+}
+
+/// 本地粗略判断一行是否已经是目标语言，或者根本没有需要翻译的文字
+fn needs_translation(text: &str, to: &str) -> bool {
+    let mut latin = 0;
+    let mut han = 0;
+    let mut kana = 0;
+    let mut hangul = 0;
+    let mut other = 0;
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => hangul += 1,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => han += 1,
+            _ if c.is_ascii_alphabetic() => latin += 1,
+            _ if c.is_alphabetic() => other += 1,
+            _ => {}
+        }
+    }
+    if latin + han + kana + hangul + other == 0 {
+        return false;
+    }
+    let already_target = match to {
+        "zh" | "cht" | "yue" | "wyw" => han > 0 && kana == 0 && han >= latin,
+        "jp" => kana > 0,
+        "kor" => hangul > 0 && hangul >= latin,
+        "en" => latin > 0 && han + kana + hangul + other == 0,
+        _ => false,
+    };
+    !already_target
+}
+
+/// 逐行读取 `reader`，发送到 `tx`，无效的 UTF-8 按替换字符处理
+pub fn spawn_reader<R: Read + Send + 'static>(reader: R, tx: Sender<String>) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = line.trim_end_matches(['\r', '\n']).to_string();
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// 从 `rx` 取行、合并、翻译并写出，直到所有发送端关闭
+///
+/// 翻译失败的行原样输出，返回第一个翻译错误，调用方据此设置退出码
+pub fn pump(
+    rx: Receiver<String>,
+    translator: &dyn Translator,
+    options: &StreamOptions,
+    out: &mut dyn Write,
+) -> io::Result<Option<BtcliError>> {
+    let mut first_error = None;
+    while let Ok(first) = rx.recv() {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + options.flush;
+        while batch.len() < options.max_lines.max(1) {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            match rx.recv_timeout(wait) {
+                Ok(line) => batch.push(line),
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        let (lines, error) = translate_lines(&batch, translator, options);
+        for line in lines {
+            writeln!(out, "{}", line)?;
+        }
+        out.flush()?;
+        if first_error.is_none() {
+            first_error = error;
+        }
+    }
+    Ok(first_error)
+}
+
+/// 翻译一批行，失败时原样输出、在标准错误提示并返回错误
+fn translate_lines(
+    batch: &[String],
+    translator: &dyn Translator,
+    options: &StreamOptions,
+) -> (Vec<String>, Option<BtcliError>) {
+    let parsed: Vec<AnsiLine> = batch.iter().map(|line| AnsiLine::parse(line)).collect();
+    let pending: Vec<usize> = (0..parsed.len())
+        .filter(|&i| needs_translation(&parsed[i].text, &options.to))
+        .collect();
+    if pending.is_empty() {
+        return (batch.to_vec(), None);
+    }
+
+    let texts: Vec<&str> = pending.iter().map(|&i| parsed[i].text.trim()).collect();
+    let mut output = batch.to_vec();
+    match translator.translate_batch(&options.from, &options.to, &texts) {
+        Ok(results) => {
+            for (&index, result) in pending.iter().zip(results) {
+                // 保留原行的缩进
+                let text = &parsed[index].text;
+                let indent = &text[..text.len() - text.trim_start().len()];
+                output[index] = parsed[index].render(&format!("{}{}", indent, result.text()));
+            }
+            (output, None)
+        }
+        Err(e) => {
+            log_to_file!("流式翻译失败: {}", e);
+            eprintln!("翻译错误: {}", e);
+            (output, Some(e))
+        }
+    }
+}
+
+/// `--stream`：把标准输入当作行流翻译到标准输出，返回第一个翻译错误
+pub fn stream_stdin(
+    translator: &dyn Translator,
+    options: &StreamOptions,
+) -> io::Result<Option<BtcliError>> {
+    let (tx, rx) = mpsc::channel();
+    spawn_reader(io::stdin(), tx);
+    pump(rx, translator, options, &mut io::stdout())
+}
+
+/// `btcli exec -- <命令>`：运行命令，把它的标准输出和标准错误逐行翻译
+///
+/// 命令失败时返回命令的退出码；命令成功但有翻译失败时返回第一个翻译错误的退出码
+pub fn exec(
+    argv: &[String],
+    translator: &dyn Translator,
+    options: &StreamOptions,
+) -> io::Result<i32> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no command given"))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_reader(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_reader(stderr, tx);
+    }
+    let error = pump(rx, translator, options, &mut io::stdout())?;
+
+    let status = child.wait()?;
+    Ok(match (status.code().unwrap_or(1), error) {
+        (0, Some(e)) => e.exit_code(),
+        (code, _) => code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translator::{Capabilities, Translation};
+
+    #[test]
+    fn test_ansi_codes_are_kept_around_text() {
+        let line = AnsiLine::parse("\x1b[1;31merror\x1b[0m");
+        assert_eq!(line.prefix, "\x1b[1;31m");
+        assert_eq!(line.text, "error");
+        assert_eq!(line.render("错误"), "\x1b[1;31m错误\x1b[0m");
+    }
+
+    struct Rejecting;
+
+    impl Translator for Rejecting {
+        fn name(&self) -> &'static str {
+            "rejecting"
+        }
+
+        fn translate(&self, _: &str, _: &str, _: &str) -> Result<Translation, BtcliError> {
+            Err(BtcliError::api(52003, ""))
+        }
+
+        fn supported_languages(&self) -> Vec<&'static str> {
+            Vec::new()
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities::default()
+        }
+    }
+
+    #[test]
+    fn test_pump_passes_lines_through_and_reports_error() {
+        let (tx, rx) = mpsc::channel();
+        tx.send("error: expected `;`".to_string()).unwrap();
+        drop(tx);
+        let mut out = Vec::new();
+        let error = pump(rx, &Rejecting, &StreamOptions::new("auto", "zh"), &mut out)
+            .unwrap()
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "error: expected `;`\n");
+        assert_eq!(error.exit_code(), crate::error::exit::AUTH);
+    }
+
+    #[test]
+    fn test_target_language_lines_pass_through() {
+        assert!(!needs_translation("编译成功", "zh"));
+        assert!(!needs_translation("  --> 42:17 ||", "zh"));
+        assert!(needs_translation("error: expected `;`", "zh"));
+        assert!(!needs_translation("already English", "en"));
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 翻译引擎抽象层
+//! CLI 和 TUI 只通过 `Translator` trait 调用翻译，具体引擎由 `AppConfig.provider` 选择
+
+use crate::conf::AppConfig;
+use crate::error::BtcliError;
+use crate::fycore::BaiduTranslator;
+use crate::pool::{JobControl, Progress};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+/// 单个翻译片段，对应原文中的一行
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TranslationItem {
+    pub src: String,
+    pub dst: String,
+    /// 原文发音链接（查词时提供）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_tts: Option<String>,
+    /// 译文发音链接（查词时提供）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dst_tts: Option<String>,
+    /// 词典释义（查词时提供）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dict: Option<DictEntry>,
+}
+
+impl TranslationItem {
+    pub fn new(src: impl Into<String>, dst: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            dst: dst.into(),
+            src_tts: None,
+            dst_tts: None,
+            dict: None,
+        }
+    }
+}
+
+/// 音标，例如 英 [həˈləʊ]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Phonetic {
+    pub label: String,
+    pub value: String,
+}
+
+/// 某一词性下的释义
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DictPart {
+    /// 词性，例如 n.、v.；中文词条可能为空
+    pub part: String,
+    pub means: Vec<String>,
+}
+
+/// 单词的词典释义
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DictEntry {
+    pub word: String,
+    pub phonetics: Vec<Phonetic>,
+    pub parts: Vec<DictPart>,
+}
+
+impl fmt::Display for DictEntry {
+    /// 渲染为词典卡片，CLI 和 TUI 共用
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.word)?;
+        if !self.phonetics.is_empty() {
+            let phonetics: Vec<String> = self
+                .phonetics
+                .iter()
+                .map(|p| format!("{} [{}]", p.label, p.value))
+                .collect();
+            writeln!(f, "  {}", phonetics.join("  "))?;
+        }
+        for part in &self.parts {
+            if part.part.is_empty() {
+                writeln!(f, "  {}", part.means.join("；"))?;
+            } else {
+                writeln!(f, "  {} {}", part.part, part.means.join("；"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一次翻译的完整结果
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    /// 实际的源语言（源语言为 auto 时为检测结果）
+    pub from: String,
+    pub to: String,
+    /// 按原文顺序排列的全部片段
+    pub items: Vec<TranslationItem>,
+    /// 结果是否来自本地缓存
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl Translation {
+    /// 按原顺序拼接所有译文，片段之间以换行分隔
+    pub fn text(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| item.dst.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 翻译引擎的能力描述
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    /// 是否支持独立的语种检测
+    pub detect: bool,
+    /// 源语言是否可以填 "auto"
+    pub auto_source: bool,
+    /// 单次请求允许的最大字节数，0 表示不限制
+    pub max_query_bytes: usize,
+    /// 是否支持垂直领域翻译
+    pub domains: bool,
+    /// 是否支持服务端术语干预（使用账户中维护的术语库）
+    pub term_intervention: bool,
+    /// 是否支持查词（词典释义和发音）
+    pub dictionary: bool,
+}
+
+/// 翻译引擎接口
+///
+/// 新引擎实现该 trait 后，通过 [`register_provider`] 注册即可被配置文件选中，
+/// 无需修改本 crate
+pub trait Translator: Send + Sync {
+    /// 引擎名称，与配置文件中的 `provider` 对应
+    fn name(&self) -> &'static str;
+
+    /// 翻译一段文本
+    fn translate(&self, from: &str, to: &str, q: &str) -> Result<Translation, BtcliError>;
+
+    /// 查词：在翻译结果之外附带词典释义和发音链接
+    ///
+    /// 不支持词典的引擎退化为普通翻译
+    fn lookup(&self, from: &str, to: &str, word: &str) -> Result<Translation, BtcliError> {
+        self.translate(from, to, word)
+    }
+
+    /// 批量翻译，结果与 `items` 一一对应
+    fn translate_batch(
+        &self,
+        from: &str,
+        to: &str,
+        items: &[&str],
+    ) -> Result<Vec<Translation>, BtcliError> {
+        self.translate_batch_with(from, to, items, &JobControl::default())
+    }
+
+    /// 带取消和进度回调的批量翻译
+    ///
+    /// 默认逐条调用 [`Translator::translate`]，支持合并请求或并发的引擎应覆盖此方法
+    fn translate_batch_with(
+        &self,
+        from: &str,
+        to: &str,
+        items: &[&str],
+        control: &JobControl,
+    ) -> Result<Vec<Translation>, BtcliError> {
+        let total = items.len();
+        let mut results = Vec::with_capacity(total);
+        for q in items {
+            if control.is_cancelled() {
+                return Err(BtcliError::Cancelled);
+            }
+            results.push(self.translate(from, to, q)?);
+            control.report(Progress {
+                done: results.len(),
+                total,
+            });
+        }
+        Ok(results)
+    }
+
+    /// 检测文本语种，返回语种代码
+    fn detect(&self, _q: &str) -> Result<String, BtcliError> {
+        Err(BtcliError::Config(format!(
+            "Provider {} does not support language detection",
+            self.name()
+        )))
+    }
+
+    /// 支持的语种代码列表
+    fn supported_languages(&self) -> Vec<&'static str>;
+
+    /// 引擎能力
+    fn capabilities(&self) -> Capabilities;
+}
+
+/// 根据配置构造翻译引擎的工厂函数
+pub type ProviderFactory = fn(&AppConfig) -> Box<dyn Translator>;
+
+lazy_static! {
+    static ref PROVIDERS: Mutex<HashMap<String, ProviderFactory>> = Mutex::new(HashMap::new());
+}
+
+/// 注册第三方翻译引擎，同名注册会覆盖之前的工厂
+pub fn register_provider(name: &str, factory: ProviderFactory) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        providers.insert(name.to_string(), factory);
+    }
+}
+
+/// 按配置中的 `provider` 字段创建翻译引擎
+pub fn from_config(config: &AppConfig) -> Result<Box<dyn Translator>, BtcliError> {
+    if let Ok(providers) = PROVIDERS.lock()
+        && let Some(factory) = providers.get(&config.provider)
+    {
+        return Ok(factory(config));
+    }
+
+    match config.provider.as_str() {
+        "baidu" | "" => Ok(Box::new(BaiduTranslator::new(config.clone()))),
+        other => Err(BtcliError::Config(format!(
+            "Unknown translation provider: {}",
+            other
+        ))),
+    }
+}
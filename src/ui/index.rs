@@ -19,12 +19,20 @@ use cursive::{
 use clipboard::{ClipboardContext, ClipboardProvider};
 
 #[cfg(feature = "ui")]
-use std::cell::Cell;
+use crate::error::BtcliError;
+#[cfg(feature = "ui")]
+use crate::pool::{CancelToken, JobControl, Progress};
+#[cfg(feature = "ui")]
+use crate::translator::Translation;
+#[cfg(feature = "ui")]
+use std::cell::{Cell, RefCell};
 
 // 使用 Cell 来安全地存储可变状态
 #[cfg(feature = "ui")]
 thread_local! {
     static ASK_ABOUT_SETTINGS: Cell<bool> = Cell::new(true);
+    // 正在后台进行的翻译任务：序号和取消标记，序号用于丢弃过期的结果
+    static CURRENT_JOB: RefCell<(u64, Option<CancelToken>)> = const { RefCell::new((0, None)) };
 }
 
 #[cfg(feature = "ui")]
@@ -49,8 +57,28 @@ pub fn build_main_view() -> LinearLayout {
                 .min_size((30, 5)),
         );
 
+    // 输入单个单词时显示词典释义
+    let dict_layout = LinearLayout::horizontal()
+        .child(TextView::new("词典: ").fixed_width(10))
+        .child(
+            TextView::new("")
+                .with_name("dict_textview")
+                .min_size((30, 1)),
+        );
+
+    // 显示识别到的源语言和目标语言
+    let lang_layout = LinearLayout::horizontal()
+        .child(TextView::new("语种: ").fixed_width(10))
+        .child(TextView::new("").with_name("lang_textview"));
+
+    // 翻译进度和状态
+    let status_layout = LinearLayout::horizontal()
+        .child(TextView::new("状态: ").fixed_width(10))
+        .child(TextView::new("").with_name("status_textview"));
+
     let button_row = LinearLayout::horizontal()
         .child(Button::new("[翻译(T)]", |s| translate_with_ask(s)))
+        .child(Button::new("[取消(X)]", cancel_translation))
         .child(Button::new("[清空(C)]", |s| clear_texts(s)))
         .child(Button::new("[查看设置(V)]", |s| {
             s.add_layer(settings::build_view_only_settings_view());
@@ -72,6 +100,9 @@ pub fn build_main_view() -> LinearLayout {
 
     layout.add_child(input_layout);
     layout.add_child(output_layout);
+    layout.add_child(lang_layout);
+    layout.add_child(dict_layout);
+    layout.add_child(status_layout);
     layout.add_child(button_row);
 
     layout
@@ -103,26 +134,132 @@ fn translate(s: &mut Cursive) {
     let config = match crate::conf::try_init_conf() {
         Ok(config) => config,
         Err(error_msg) => {
-            lovely_items::show_error(s, &error_msg);
+            lovely_items::show_error(s, &error_msg.to_string());
+            return;
+        }
+    };
+
+    // 按配置选择翻译引擎
+    let translator = match crate::translator::from_config(&config) {
+        Ok(translator) => translator,
+        Err(error_msg) => {
+            lovely_items::show_error(s, &error_msg.to_string());
             return;
         }
     };
 
-    // 执行翻译
-    match crate::fycore::translate(
-        &config.appid,
-        &config.source_lang,
-        &config.target_lang,
-        &input_content,
-        config.clone(),
-    ) {
+    // 取消仍在进行的上一次翻译，新任务在后台线程执行，界面保持响应
+    let cancel = CancelToken::new();
+    let seq = CURRENT_JOB.with(|job| {
+        let mut job = job.borrow_mut();
+        if let Some(previous) = job.1.take() {
+            previous.cancel();
+        }
+        job.0 += 1;
+        job.1 = Some(cancel.clone());
+        job.0
+    });
+    set_status(s, "翻译中…");
+
+    let sink = s.cb_sink().clone();
+    let progress_sink = sink.clone();
+    let control = JobControl::new()
+        .with_cancel(cancel)
+        .with_progress(move |progress: Progress| {
+            if progress.total > 1 {
+                let status = format!("翻译中… {}/{}", progress.done, progress.total);
+                let _ = progress_sink.send(Box::new(move |s| {
+                    if is_current_job(seq) {
+                        set_status(s, &status);
+                    }
+                }));
+            }
+        });
+
+    // 单个单词走查词接口以便显示词典释义
+    let use_dict = is_single_word(&input_content) && translator.capabilities().dictionary;
+    std::thread::spawn(move || {
+        let (from, to) = (&config.source_lang, &config.target_lang);
+        let outcome = if use_dict {
+            translator.lookup(from, to, &input_content)
+        } else {
+            translator
+                .translate_batch_with(from, to, &[&input_content], &control)
+                .and_then(|mut results| {
+                    results
+                        .pop()
+                        .ok_or_else(|| BtcliError::Parse("empty translation result".to_string()))
+                })
+        };
+        let _ = sink.send(Box::new(move |s| {
+            if is_current_job(seq) {
+                show_outcome(s, outcome);
+            }
+        }));
+    });
+}
+
+#[cfg(feature = "ui")]
+fn is_current_job(seq: u64) -> bool {
+    CURRENT_JOB.with(|job| job.borrow().0 == seq)
+}
+
+#[cfg(feature = "ui")]
+fn set_status(s: &mut Cursive, status: &str) {
+    s.call_on_name("status_textview", |view: &mut TextView| {
+        view.set_content(status);
+    });
+}
+
+// 取消正在进行的翻译
+#[cfg(feature = "ui")]
+pub fn cancel_translation(s: &mut Cursive) {
+    let cancelled = CURRENT_JOB.with(|job| match job.borrow_mut().1.take() {
+        Some(cancel) => {
+            cancel.cancel();
+            true
+        }
+        None => false,
+    });
+    if cancelled {
+        set_status(s, "正在取消…");
+    }
+}
+
+// 显示后台翻译的结果
+#[cfg(feature = "ui")]
+fn show_outcome(s: &mut Cursive, outcome: Result<Translation, BtcliError>) {
+    CURRENT_JOB.with(|job| job.borrow_mut().1 = None);
+    match outcome {
         Ok(result) => {
+            set_status(
+                s,
+                if result.cached {
+                    "完成（缓存）"
+                } else {
+                    "完成"
+                },
+            );
             s.call_on_name("output_textview", |view: &mut TextView| {
-                view.set_content(&result);
+                view.set_content(result.text());
+            });
+            s.call_on_name("lang_textview", |view: &mut TextView| {
+                view.set_content(format!("{} -> {}", result.from, result.to));
+            });
+            let dict_card: String = result
+                .items
+                .iter()
+                .filter_map(|item| item.dict.as_ref())
+                .map(|dict| dict.to_string())
+                .collect();
+            s.call_on_name("dict_textview", |view: &mut TextView| {
+                view.set_content(dict_card);
             });
         }
+        Err(BtcliError::Cancelled) => set_status(s, "已取消"),
         Err(error_msg) => {
-            lovely_items::show_error(s, &error_msg);
+            set_status(s, "失败");
+            lovely_items::show_translate_error(s, &error_msg);
         }
     }
 }
@@ -160,10 +297,30 @@ pub fn translate_with_ask(s: &mut Cursive) {
 
 #[cfg(feature = "ui")]
 pub fn clear_texts(s: &mut Cursive) {
+    cancel_translation(s);
+    set_status(s, "");
     s.call_on_name("input_textarea", |view: &mut TextArea| view.set_content(""));
     s.call_on_name("output_textview", |view: &mut TextView| {
         view.set_content("");
     });
+    s.call_on_name("lang_textview", |view: &mut TextView| {
+        view.set_content("");
+    });
+    s.call_on_name("dict_textview", |view: &mut TextView| {
+        view.set_content("");
+    });
+}
+
+// 判断输入是否为单个西文单词（字母开头，只含 ASCII 字母、连字符和撇号，且较短）
+// 中日文句子不以空格分词，不能只看有没有空白
+#[cfg(feature = "ui")]
+fn is_single_word(text: &str) -> bool {
+    let text = text.trim();
+    text.len() <= 32
+        && text.starts_with(|c: char| c.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '-' || c == '\'')
 }
 
 // 复制翻译结果到剪贴板
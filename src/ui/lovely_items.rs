@@ -4,6 +4,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+#[cfg(feature = "ui")]
+use crate::error::BtcliError;
 #[cfg(feature = "ui")]
 use cursive::{
     Cursive,
@@ -36,6 +38,32 @@ pub fn show_error(s: &mut Cursive, error_message: &str) {
     s.add_layer(messagebox("错误", error_message));
 }
 
+/// 按错误类别显示翻译错误
+///
+/// 认证类错误额外提供“修改设置”按钮，其余错误附加处理建议
+#[cfg(feature = "ui")]
+pub fn show_translate_error(s: &mut Cursive, error: &BtcliError) {
+    let message = match error.hint() {
+        Some(hint) => format!("{}\n\n提示: {}", error, hint),
+        None => error.to_string(),
+    };
+
+    let mut dialog = messagebox("错误", &message);
+    if error.is_auth() {
+        dialog.add_button("修改设置", |s| {
+            s.pop_layer();
+            s.add_layer(crate::ui::settings::build_settings_view());
+            // 延迟填充设置，确保UI控件已完全加载
+            s.cb_sink()
+                .send(Box::new(|s| {
+                    crate::ui::settings::populate_settings_view(s);
+                }))
+                .unwrap_or(());
+        });
+    }
+    s.add_layer(dialog);
+}
+
 #[cfg(feature = "ui")]
 pub fn show_info(s: &mut Cursive, error_message: &str) {
     s.add_layer(messagebox("提示", error_message));
@@ -11,7 +11,7 @@ use cursive::Cursive;
 #[cfg(feature = "ui")]
 use cursive::traits::{Nameable, Resizable};
 #[cfg(feature = "ui")]
-use cursive::views::{Button, Checkbox, Dialog, EditView, LinearLayout, TextView};
+use cursive::views::{Button, Checkbox, Dialog, EditView, LinearLayout, SelectView, TextView};
 
 #[cfg(feature = "ui")]
 use log::debug;
@@ -41,6 +41,11 @@ pub fn build_settings_view() -> Dialog {
                 .child(TextView::new("目标语言: ").fixed_width(10))
                 .child(EditView::new().with_name("target_lang").fixed_width(20)),
         )
+        .child(
+            LinearLayout::horizontal()
+                .child(TextView::new("翻译领域: ").fixed_width(10))
+                .child(build_domain_select().with_name("domain").fixed_width(20)),
+        )
         .child(
             LinearLayout::horizontal()
                 .child(TextView::new("开启调试: ").fixed_width(10))
@@ -65,6 +70,30 @@ pub fn build_settings_view() -> Dialog {
     dialog
 }
 
+// 领域选择器，第一项“通用”对应空字符串
+#[cfg(feature = "ui")]
+fn build_domain_select() -> SelectView<String> {
+    let mut select = SelectView::new().popup();
+    select.add_item("通用", String::new());
+    for (code, name) in crate::fycore::BAIDU_DOMAINS {
+        select.add_item(format!("{} ({})", name, code), code.to_string());
+    }
+    select
+}
+
+// 把领域代码转换为界面上显示的名称
+#[cfg(feature = "ui")]
+fn domain_label(domain: Option<&str>) -> String {
+    match domain {
+        Some(code) if !code.is_empty() => crate::fycore::BAIDU_DOMAINS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(c, name)| format!("{} ({})", name, c))
+            .unwrap_or_else(|| code.to_string()),
+        _ => "通用".to_string(),
+    }
+}
+
 // 添加只读设置视图
 #[cfg(feature = "ui")]
 pub fn build_view_only_settings_view() -> Dialog {
@@ -97,6 +126,11 @@ pub fn build_view_only_settings_view() -> Dialog {
                         .fixed_width(20),
                 ),
         )
+        .child(
+            LinearLayout::horizontal()
+                .child(TextView::new("翻译领域: ").fixed_width(10))
+                .child(TextView::new("").with_name("view_domain").fixed_width(20)),
+        )
         .child(
             LinearLayout::horizontal()
                 .child(TextView::new("开启调试: ").fixed_width(10))
@@ -227,6 +261,16 @@ pub fn populate_settings_view(s: &mut Cursive) {
                 log_to_file!("更新目标语言字段失败 - 控件可能不存在");
             }
 
+            let domain = config.domain.clone().unwrap_or_default();
+            s.call_on_name("domain", |view: &mut SelectView<String>| {
+                let index = view
+                    .iter()
+                    .position(|(_, code)| *code == domain)
+                    .unwrap_or(0);
+                log_to_file!("设置领域字段: {}", domain);
+                view.set_selection(index);
+            });
+
             if let Some(_) = s.call_on_name("enable_debug", |view: &mut Checkbox| {
                 debug!("Setting enable_debug checkbox: {}", config.enable_logging);
                 log_to_file!("设置调试复选框: {}", config.enable_logging);
@@ -328,6 +372,10 @@ pub fn populate_view_only_settings_view(s: &mut Cursive) {
                 log_to_file!("更新只读目标语言字段失败 - 控件可能不存在");
             }
 
+            s.call_on_name("view_domain", |view: &mut TextView| {
+                view.set_content(domain_label(config.domain.as_deref()));
+            });
+
             if let Some(_) = s.call_on_name("view_enable_debug", |view: &mut TextView| {
                 let debug_status = if config.enable_logging { "是" } else { "否" };
                 debug!("Setting view_enable_debug field: {}", debug_status);
@@ -358,6 +406,9 @@ pub fn populate_view_only_settings_view(s: &mut Cursive) {
             s.call_on_name("view_target_lang", |view: &mut TextView| {
                 view.set_content(&error_msg);
             });
+            s.call_on_name("view_domain", |view: &mut TextView| {
+                view.set_content("未设置");
+            });
             s.call_on_name("view_enable_debug", |view: &mut TextView| {
                 view.set_content("未设置");
             });
@@ -396,6 +447,12 @@ fn save_settings(s: &mut Cursive) {
     let enable_debug = s
         .call_on_name("enable_debug", |view: &mut Checkbox| view.is_checked())
         .unwrap_or_default();
+    let domain = s
+        .call_on_name("domain", |view: &mut SelectView<String>| {
+            view.selection().map(|code| code.to_string())
+        })
+        .flatten()
+        .filter(|code| !code.is_empty());
 
     log_to_file!(
         "从UI获取的设置 - AppID: {}, Key长度: {}, SourceLang: {}, TargetLang: {}, EnableDebug: {}",
@@ -413,9 +470,25 @@ fn save_settings(s: &mut Cursive) {
         return;
     }
 
-    // 保存到配置
-    match crate::conf::save_conf_with_debug(&appid, &key, &source_lang, &target_lang, enable_debug)
-    {
+    // 保存到配置，界面上没有的字段沿用当前配置；当前配置读取失败时不保存，避免覆盖其他设置
+    let current = match crate::conf::try_init_conf() {
+        Ok(current) => current,
+        Err(e) => {
+            log_to_file!("读取当前配置失败: {}", e);
+            lovely_items::show_error(s, &format!("读取当前配置失败，未保存: {}", e));
+            return;
+        }
+    };
+    let config = crate::conf::AppConfig {
+        appid: appid.to_string(),
+        key: key.to_string(),
+        source_lang: source_lang.to_string(),
+        target_lang: target_lang.to_string(),
+        enable_logging: enable_debug,
+        domain,
+        ..current
+    };
+    match crate::conf::save_conf(&config) {
         Ok(_) => {
             log_to_file!(
                 "配置保存成功 - AppID: {}, SourceLang: {}, TargetLang: {}",
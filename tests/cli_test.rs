@@ -4,126 +4,145 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-// CLI测试 - 由于模块结构限制，这里主要是验证功能的注释说明
-// 实际功能已在应用运行时验证
-
-/*
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_basic_text() {
-        let args = vec!["hello".to_string(), "world".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "hello world");
-        assert_eq!(result.source_lang, None);
-        assert_eq!(result.target_lang, None);
-        assert!(!result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_with_source_lang() {
-        let args = vec!["-s".to_string(), "en".to_string(), "hello world".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "hello world");
-        assert_eq!(result.source_lang, Some("en".to_string()));
-        assert_eq!(result.target_lang, None);
-        assert!(!result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_with_target_lang() {
-        let args = vec!["-t".to_string(), "zh".to_string(), "hello world".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "hello world");
-        assert_eq!(result.source_lang, None);
-        assert_eq!(result.target_lang, Some("zh".to_string()));
-        assert!(!result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_with_both_langs() {
-        let args = vec![
-            "-s".to_string(), 
-            "en".to_string(), 
-            "-t".to_string(), 
-            "zh".to_string(), 
-            "hello world".to_string()
-        ];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "hello world");
-        assert_eq!(result.source_lang, Some("en".to_string()));
-        assert_eq!(result.target_lang, Some("zh".to_string()));
-        assert!(!result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_help_flag_short() {
-        let args = vec!["-h".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "");
-        assert_eq!(result.source_lang, None);
-        assert_eq!(result.target_lang, None);
-        assert!(result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_help_flag_long() {
-        let args = vec!["--help".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "");
-        assert_eq!(result.source_lang, None);
-        assert_eq!(result.target_lang, None);
-        assert!(result.help);
-        assert!(!result.version);
-    }
-
-    #[test]
-    fn test_parse_version_flag() {
-        let args = vec!["--version".to_string()];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "");
-        assert_eq!(result.source_lang, None);
-        assert_eq!(result.target_lang, None);
-        assert!(!result.help);
-        assert!(result.version);
-    }
-
-    #[test]
-    fn test_parse_complex_args() {
-        let args = vec![
-            "-s".to_string(), 
-            "en".to_string(), 
-            "this is a complex sentence with multiple words".to_string()
-        ];
-        let result = crate::cli::parse_args(&args);
-        
-        assert_eq!(result.text, "this is a complex sentence with multiple words");
-        assert_eq!(result.source_lang, Some("en".to_string()));
-        assert_eq!(result.target_lang, None);
-        assert!(!result.help);
-        assert!(!result.version);
-    }
+//! 命令行参数解析测试
+
+use btcli_lib::cli::{CacheAction, CliError, Command, StandaloneCommand, parse_args};
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_basic_text() {
+    let result = parse_args(&args(&["hello", "world"])).unwrap();
+
+    assert_eq!(result.text, "hello world");
+    assert_eq!(result.source_lang, None);
+    assert_eq!(result.target_lang, None);
+    assert!(!result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_with_source_lang() {
+    let result = parse_args(&args(&["-s", "en", "hello world"])).unwrap();
+
+    assert_eq!(result.text, "hello world");
+    assert_eq!(result.source_lang, Some("en".to_string()));
+    assert_eq!(result.target_lang, None);
+    assert!(!result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_with_target_lang() {
+    let result = parse_args(&args(&["-t", "zh", "hello world"])).unwrap();
+
+    assert_eq!(result.text, "hello world");
+    assert_eq!(result.source_lang, None);
+    assert_eq!(result.target_lang, Some("zh".to_string()));
+    assert!(!result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_with_both_langs() {
+    let result = parse_args(&args(&["-s", "en", "-t", "zh", "hello world"])).unwrap();
+
+    assert_eq!(result.text, "hello world");
+    assert_eq!(result.source_lang, Some("en".to_string()));
+    assert_eq!(result.target_lang, Some("zh".to_string()));
+    assert!(!result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_help_flag_short() {
+    let result = parse_args(&args(&["-h"])).unwrap();
+
+    assert_eq!(result.text, "");
+    assert_eq!(result.source_lang, None);
+    assert_eq!(result.target_lang, None);
+    assert!(result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_help_flag_long() {
+    let result = parse_args(&args(&["--help"])).unwrap();
+
+    assert_eq!(result.text, "");
+    assert!(result.help);
+    assert!(!result.version);
+}
+
+#[test]
+fn test_parse_version_flag() {
+    let result = parse_args(&args(&["--version"])).unwrap();
+
+    assert_eq!(result.text, "");
+    assert!(!result.help);
+    assert!(result.version);
+}
+
+#[test]
+fn test_parse_complex_args() {
+    let result = parse_args(&args(&[
+        "-s",
+        "en",
+        "this is a complex sentence with multiple words",
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        result.text,
+        "this is a complex sentence with multiple words"
+    );
+    assert_eq!(result.source_lang, Some("en".to_string()));
+    assert_eq!(result.target_lang, None);
+}
+
+#[test]
+fn test_unknown_option_suggests_closest() {
+    let error = parse_args(&args(&["--targte", "zh", "hello"])).unwrap_err();
+    assert_eq!(
+        error,
+        CliError::UnknownOption {
+            option: "--targte".to_string(),
+            suggestion: Some("--target".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_double_dash_ends_options() {
+    let result = parse_args(&args(&["-t", "zh", "--", "--help", "cache"])).unwrap();
+    assert_eq!(result.text, "--help cache");
+    assert!(!result.help);
+    assert_eq!(result.command, None);
+}
+
+#[test]
+fn test_subcommands() {
+    let result = parse_args(&args(&["cache", "clear"])).unwrap();
+    assert_eq!(result.command, Some(Command::Cache(CacheAction::Clear)));
+
+    let result = parse_args(&args(&["exec", "-t", "zh", "cargo", "build", "-q"])).unwrap();
+    assert_eq!(result.target_lang, Some("zh".to_string()));
+    assert_eq!(
+        result.command,
+        Some(Command::Exec(args(&["cargo", "build", "-q"])))
+    );
+
+    let result = parse_args(&args(&["config", "path"])).unwrap();
+    assert_eq!(result.standalone, Some(StandaloneCommand::ConfigPath));
+    assert_eq!(result.command, None);
+
+    assert!(matches!(
+        parse_args(&args(&["cache", "stat"])),
+        Err(CliError::UnknownAction {
+            suggestion: Some("stats"),
+            ..
+        })
+    ));
 }
-*/
-
-// 该测试文件保留作为CLI功能的文档说明
-// CLI功能包括：
-// - 基础文本翻译: btcli "text to translate"
-// - 指定目标语言: btcli -t zh "text"
-// - 指定源语言和目标语言: btcli -s en -t zh "text"
-// - 显示帮助: btcli --help
-// - 显示版本: btcli --version
\ No newline at end of file
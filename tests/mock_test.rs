@@ -0,0 +1,102 @@
+// Copyright (C) 2026 S.A. (@snoware)
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+// 集成测试：通过 btcli-mock 走完整的 HTTP 请求、签名校验和响应解析
+
+use btcli_lib::conf::{AccountTier, AppConfig, CacheConfig, RetryPolicy};
+use btcli_lib::error::BtcliError;
+use btcli_lib::fancy_egg::{EGG_CODE, decrypt};
+use btcli_lib::fycore::BaiduTranslator;
+use btcli_lib::mock::{MockOptions, MockServer};
+use btcli_lib::translator::Translator;
+
+fn start(appid: &str) -> (MockServer, AppConfig) {
+    let options = MockOptions {
+        appid: appid.to_string(),
+        ..MockOptions::default()
+    };
+    let server = MockServer::start("127.0.0.1:0", options.clone()).unwrap();
+    let config = AppConfig {
+        appid: options.appid,
+        key: options.key,
+        tier: AccountTier::Premium,
+        api_base: Some(server.url()),
+        cache: CacheConfig {
+            enabled: false,
+            ..CacheConfig::default()
+        },
+        retry: RetryPolicy {
+            base_delay_ms: 10,
+            max_delay_ms: 20,
+            long_query_delay_ms: 10,
+            ..RetryPolicy::default()
+        },
+        ..AppConfig::default()
+    };
+    (server, config)
+}
+
+#[test]
+fn test_translate_through_mock() {
+    let (_server, config) = start("mock-translate");
+    let translator = BaiduTranslator::new(config);
+    let result = translator.translate("auto", "zh", "Hello\nWorld").unwrap();
+    assert_eq!(result.from, "en");
+    assert_eq!(result.text(), "[zh] Hello\n[zh] World");
+}
+
+#[test]
+fn test_batch_keeps_easter_egg() {
+    let (_server, config) = start("mock-egg");
+    let translator = BaiduTranslator::new(config);
+    let results = translator
+        .translate_batch("auto", "zh", &["QAS", "Hello"])
+        .unwrap();
+    assert_eq!(results[0].text(), decrypt(EGG_CODE));
+    assert_eq!(results[1].text(), "[zh] Hello");
+}
+
+#[test]
+fn test_detect_through_mock() {
+    let (_server, config) = start("mock-detect");
+    let translator = BaiduTranslator::new(config);
+    assert_eq!(translator.detect("こんにちは").unwrap(), "jp");
+}
+
+#[test]
+fn test_wrong_key_is_rejected_by_sign_check() {
+    let (_server, mut config) = start("mock-sign");
+    config.key = "wrong-key".to_string();
+    let translator = BaiduTranslator::new(config);
+    let err = translator.translate("en", "zh", "Hello").unwrap_err();
+    assert!(matches!(err, BtcliError::Api { code: 54001, .. }));
+}
+
+#[test]
+fn test_injected_transient_error_is_retried() {
+    let (server, config) = start("mock-retry");
+    server.inject(52001);
+    let translator = BaiduTranslator::new(config);
+    assert_eq!(
+        translator.translate("en", "zh", "Hello").unwrap().text(),
+        "[zh] Hello"
+    );
+    assert_eq!(server.request_count(), 2);
+}
+
+#[test]
+fn test_batch_packs_items_into_one_request() {
+    let (server, config) = start("mock-batch");
+    let translator = BaiduTranslator::new(config);
+    let items = ["Hello", "", "Two\nlines", "World"];
+    let results = translator.translate_batch("en", "zh", &items).unwrap();
+    let texts: Vec<String> = results.iter().map(|r| r.text()).collect();
+    assert_eq!(
+        texts,
+        vec!["[zh] Hello", "", "[zh] Two\n[zh] lines", "[zh] World"]
+    );
+    assert_eq!(server.request_count(), 1);
+}